@@ -9,9 +9,9 @@ BACKGROUND_DIR = "../test_assets/background.png"  # Replace with the path to you
 
 # Load image and create map
 image = Image.open(LOCAL_DIR).convert("RGBA")
-image_bytes = list(image.tobytes())
+image_bytes = image.tobytes()
 background = Image.open(BACKGROUND_DIR).convert("RGBA")
-background_bytes = list(background.tobytes())
+background_bytes = background.tobytes()
 map = Map(
     image_bytes, 
     image.size[0], 
@@ -78,7 +78,7 @@ while running:
         # Clear the screen
         screen.fill((0, 0, 0))
         try:
-            travel = Travel(map, (player_x, player_y), (map_x, map_y))
+            travel = Travel(map, (player_x, player_y), (map_x, map_y), snap_destination=True)
             map_bits = Map.draw_background(
                     map.with_dot(player_x, player_y, (255, 0, 0, 255), 5).draw_path(
                         travel,
@@ -9,10 +9,10 @@ START_X, START_Y = START
 
 image = Image.open(LOCAL_DIR).convert("RGBA")
 # get image bytes
-image_bytes = list(image.tobytes())
+image_bytes = image.tobytes()
 background = Image.open(BACKGROUND_DIR).convert("RGBA")
 # get background bytes
-background_bytes = list(background.tobytes())
+background_bytes = background.tobytes()
 map = Map(
     image_bytes,
     image.size[0],
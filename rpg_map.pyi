@@ -2,6 +2,8 @@
 # ruff: noqa: E501, F401
 
 import builtins
+import os
+import pathlib
 import typing
 from enum import Enum
 
@@ -11,8 +13,9 @@ class Map:
     
     Parameters
     ----------
-    bytes : List[int]
-        The bytes of the image.
+    bytes : bytes
+        The RGBA bytes of the image. Any object supporting the buffer protocol,
+        like a ``bytearray``, ``memoryview`` or NumPy array, can be passed as well.
     width : int
         The width of the image.
     height : int
@@ -25,7 +28,8 @@ class Map:
     unlocked : List[Tuple[int, int]]
         The points that are unlocked on the map.
     special_points : List[Tuple[int, int]]
-        The special points on the map. Used to draw the path.
+        The special points on the map. Used to draw the path. They reveal a larger area,
+        which can be changed with :func:`Map.with_reveal_shape`.
     obstacles : List[List[List[Tuple[int, int]]]]
         The obstacles on the map. Used to draw the path.
     background : Optional[List[int]]
@@ -38,23 +42,171 @@ class Map:
         The height of the map.
     unlocked : List[Tuple[int, int]]
         The points that are unlocked on the map.
+    
+    Raises
+    ------
+    SizeMismatchError
+        If there are not exactly ``width * height * 4`` bytes.
+    OutOfBoundsError
+        If an unlocked point, special point or obstacle point is outside of the map.
+    ValueError
+        If the width, height or grid size is 0.
     """
     width: builtins.int
     height: builtins.int
     unlocked: builtins.list[tuple[builtins.int, builtins.int]]
-    def __new__(cls, bytes:typing.Sequence[builtins.int], width:builtins.int, height:builtins.int, grid_size:builtins.int, map_type:MapType=MapType.Full, unlocked:typing.Sequence[tuple[builtins.int, builtins.int]]=[], special_points:typing.Sequence[tuple[builtins.int, builtins.int]]=[], obstacles:typing.Sequence[typing.Sequence[tuple[builtins.int, builtins.int]]]=[]) -> Map: ...
+    unlocked_regions: builtins.list[builtins.str]
+    r"""
+    The names of the regions which are unlocked.
+    """
+    current_position: typing.Optional[tuple[builtins.int, builtins.int]]
+    r"""
+    The grid point which was unlocked last. On an explored map this is the area which is currently visible.
+    """
+    def __new__(cls, bytes:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]], width:builtins.int, height:builtins.int, grid_size:builtins.int, map_type:MapType=MapType.Full, unlocked:typing.Sequence[tuple[builtins.int, builtins.int]]=[], special_points:typing.Sequence[tuple[builtins.int, builtins.int]]=[], obstacles:typing.Sequence[typing.Sequence[tuple[builtins.int, builtins.int]]]=[]) -> Map: ...
+    @staticmethod
+    def from_file(path:builtins.str | os.PathLike | pathlib.Path, grid_size:builtins.int, map_type:MapType=MapType.Full, unlocked:typing.Sequence[tuple[builtins.int, builtins.int]]=[], special_points:typing.Sequence[tuple[builtins.int, builtins.int]]=[], obstacles:typing.Sequence[typing.Sequence[tuple[builtins.int, builtins.int]]]=[]) -> Map:
+        r"""
+        Creates a map from a PNG or WebP file. Only available if rpg_map was built with the ``image`` feature.
+        
+        Parameters
+        ----------
+        path : str
+            The path of the image file.
+        grid_size : int
+            The size of a single box in the grid, see :class:`Map`.
+        map_type : MapType
+            The type of the map. Can be Hidden, Limited or Full.
+        unlocked : List[Tuple[int, int]]
+            The points that are unlocked on the map.
+        special_points : List[Tuple[int, int]]
+            The special points on the map.
+        obstacles : List[List[Tuple[int, int]]]
+            The obstacles on the map.
+        
+        Returns
+        -------
+        Map
+            The map with the size and bytes of the image.
+        """
+    @staticmethod
+    def from_png_bytes(data:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]], grid_size:builtins.int, map_type:MapType=MapType.Full, unlocked:typing.Sequence[tuple[builtins.int, builtins.int]]=[], special_points:typing.Sequence[tuple[builtins.int, builtins.int]]=[], obstacles:typing.Sequence[typing.Sequence[tuple[builtins.int, builtins.int]]]=[]) -> Map:
+        r"""
+        Creates a map from the contents of a PNG (or WebP) file. Only available if rpg_map was built with the ``image`` feature.
+        
+        Parameters
+        ----------
+        data : bytes
+            The encoded image.
+        grid_size : int
+            The size of a single box in the grid, see :class:`Map`.
+        map_type : MapType
+            The type of the map. Can be Hidden, Limited or Full.
+        unlocked : List[Tuple[int, int]]
+            The points that are unlocked on the map.
+        special_points : List[Tuple[int, int]]
+            The special points on the map.
+        obstacles : List[List[Tuple[int, int]]]
+            The obstacles on the map.
+        
+        Returns
+        -------
+        Map
+            The map with the size and bytes of the image.
+        """
+    def to_json(self, include_bytes:builtins.bool=True) -> builtins.str:
+        r"""
+        Serializes the full state of the map, including the unlocked points, obstacles,
+        dots and draw settings, to JSON. It can be restored with :func:`Map.from_json`.
+        
+        Parameters
+        ----------
+        include_bytes : bool
+            If false, the bytes of the image are left out to keep the result small. Only a hash of them is stored,
+            so they have to be passed to :func:`Map.from_json` again.
+        
+        Returns
+        -------
+        str
+            The state of the map as JSON.
+        """
     @staticmethod
-    def draw_background(bytes:typing.Sequence[builtins.int], background:typing.Sequence[builtins.int]) -> builtins.list[builtins.int]:
+    def from_json(data:builtins.str, bytes:typing.Optional[typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]]=None) -> Map:
+        r"""
+        Restores a map serialized with :func:`Map.to_json`.
+        
+        Parameters
+        ----------
+        data : str
+            The JSON created by :func:`Map.to_json`.
+        bytes : Optional[bytes]
+            The bytes of the image. Required if the map was serialized without them.
+        
+        Returns
+        -------
+        Map
+            The restored map.
+        """
+    def to_binary(self, include_bytes:builtins.bool=True) -> bytes:
+        r"""
+        Serializes the full state of the map to a compact binary format.
+        It can be restored with :func:`Map.from_binary`.
+        
+        Parameters
+        ----------
+        include_bytes : bool
+            If false, the bytes of the image are left out, see :func:`Map.to_json`.
+        
+        Returns
+        -------
+        bytes
+            The state of the map.
+        """
+    @staticmethod
+    def from_binary(data:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]], bytes:typing.Optional[typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]]=None) -> Map:
+        r"""
+        Restores a map serialized with :func:`Map.to_binary`.
+        
+        Parameters
+        ----------
+        data : bytes
+            The bytes created by :func:`Map.to_binary`.
+        bytes : Optional[bytes]
+            The bytes of the image. Required if the map was serialized without them.
+        
+        Returns
+        -------
+        Map
+            The restored map.
+        """
+    def __reduce__(self) -> tuple[typing.Any, tuple]:
+        r"""
+        Pickles the map with its full state, see :func:`Map.to_binary`
+        """
+    def __copy__(self) -> Map: ...
+    def __deepcopy__(self, _memo:typing.Any) -> Map: ...
+    @staticmethod
+    def draw_background(bytes:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]], background:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]) -> bytes:
         r"""
         Draws the background image at every transparent pixel
         if the background is set
         
         Parameters
         ----------
-        bytes : List[int]
+        bytes : bytes
             The bytes of the image.
-        background : Optional[List[int]]
+        background : bytes
             The bytes of the background of the image.
+        
+        Returns
+        -------
+        bytes
+            The bytes of the image with the background.
+        
+        Raises
+        ------
+        SizeMismatchError
+            If the background does not have the same size as the image.
         """
     def with_dot(self, x:builtins.int, y:builtins.int, color:typing.Sequence[builtins.int], radius:builtins.int) -> Map:
         r"""
@@ -76,6 +228,22 @@ class Map:
         Map
             The map with the dot.
         """
+    def with_movement_range(self, movement_range:MovementRange, color:typing.Sequence[builtins.int]) -> Map:
+        r"""
+        Tints all points of a movement range to be drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
+        
+        Parameters
+        ----------
+        movement_range : MovementRange
+            The movement range to draw, see :func:`Travel.movement_range`.
+        color : Tuple[int, int, int, int]
+            The color of the tint. Its alpha value is the opacity of the tint.
+        
+        Returns
+        -------
+        Map
+            The map with the movement range.
+        """
     def with_grid(self) -> Map:
         r"""
         If called, a grid is drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
@@ -84,11 +252,247 @@ class Map:
         r"""
         If called, the obstacles are drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
         """
+    def with_cost_map(self, cost_map:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]) -> Map:
+        r"""
+        Sets a terrain cost layer which :class:`Travel` uses to weigh each step of the path.
+        Paths prefer cheap terrain (like roads) and avoid expensive terrain (like swamps or mountains)
+        without treating it as an obstacle.
+        
+        Parameters
+        ----------
+        cost_map : bytes
+            The bytes of an image with the same size as the map. The brightness of each pixel is its movement cost,
+            where black is the cheapest (a cost of 1) and white the most expensive (a cost of 255).
+            Fully transparent pixels have a cost of 1.
+        
+        Returns
+        -------
+        Map
+            The map with the cost layer.
+        
+        Raises
+        ------
+        SizeMismatchError
+            If the cost map does not have the same size as the map.
+        """
+    def with_buffer(self, edges:builtins.int, obstacles:typing.Optional[builtins.int]=None, overrides:typing.Mapping[builtins.int, builtins.int]={}) -> Map:
+        r"""
+        Sets how much clearance paths keep from obstacles and the edges of the map.
+        Use a small buffer to keep narrow passes open or a large one for travellers who need more room.
+        
+        Parameters
+        ----------
+        edges : int
+            The clearance in pixels from transparent pixels, i.e. the edges of the map. Defaults to 5.
+        obstacles : Optional[int]
+            The clearance in pixels from the polygon obstacles. Defaults to the clearance of the edges.
+        overrides : Dict[int, int]
+            The clearance of single obstacles, keyed by their index in the obstacles of the map.
+        
+        Returns
+        -------
+        Map
+            The map with the buffers.
+        """
+    def with_fog_feather(self, width:builtins.int) -> Map:
+        r"""
+        Fades the revealed areas smoothly into the fog instead of cutting them out with a hard edge.
+        
+        Parameters
+        ----------
+        width : int
+            The width in pixels of the fade at the edge of every revealed circle. Defaults to 0, a hard edge.
+        
+        Returns
+        -------
+        Map
+            The map with the feathered fog.
+        """
+    def with_fog_color(self, color:typing.Sequence[builtins.int]) -> Map:
+        r"""
+        Covers the hidden areas of the map with a color instead of black.
+        
+        Parameters
+        ----------
+        color : Tuple[int, int, int, int]
+            The RGBA color of the fog. With an alpha below 255 the map shows through the fog.
+        
+        Returns
+        -------
+        Map
+            The map with the colored fog.
+        """
+    def with_fog_texture(self, texture:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]], width:builtins.int, height:builtins.int) -> Map:
+        r"""
+        Covers the hidden areas of the map with a texture, like old parchment, which is repeated over the map.
+        
+        Parameters
+        ----------
+        texture : bytes
+            The RGBA bytes of the texture. Transparent pixels of the texture let the map show through.
+        width : int
+            The width of the texture.
+        height : int
+            The height of the texture.
+        
+        Returns
+        -------
+        Map
+            The map with the textured fog.
+        
+        Raises
+        ------
+        ValueError
+            If the texture does not have ``width * height * 4`` bytes.
+        """
+    def with_fog_shade(self, brightness:builtins.float, saturation:builtins.float) -> Map:
+        r"""
+        Shows a darkened and desaturated version of the map in the hidden areas instead of covering them.
+        
+        Parameters
+        ----------
+        brightness : float
+            How bright the hidden areas are, from 0 (black) to 1 (unchanged).
+        saturation : float
+            How colorful the hidden areas are, from 0 (grayscale) to 1 (unchanged).
+        
+        Returns
+        -------
+        Map
+            The map with the shaded fog.
+        
+        Raises
+        ------
+        ValueError
+            If the brightness or saturation is not between 0 and 1.
+        """
+    def with_explored_fog(self, opacity:builtins.float) -> Map:
+        r"""
+        Sets how much fog covers the explored areas of a map with the ``MapType.Explored`` type
+        which are not visible from the current position.
+        
+        Parameters
+        ----------
+        opacity : float
+            How much fog covers the explored areas, from 0 (fully visible) to 1 (as hidden as unexplored areas).
+            Defaults to 0.5.
+        
+        Returns
+        -------
+        Map
+            The map with the explored fog.
+        
+        Raises
+        ------
+        ValueError
+            If the opacity is not between 0 and 1.
+        """
+    def with_line_of_sight(self, enabled:builtins.bool=True) -> Map:
+        r"""
+        Only reveals what can be seen from the unlocked points instead of a full circle around them.
+        Obstacles and transparent pixels block the view, so rooms behind walls stay hidden.
+        
+        Parameters
+        ----------
+        enabled : bool
+            Whether the view is blocked by obstacles and transparent pixels. Defaults to True.
+        
+        Returns
+        -------
+        Map
+            The map with line of sight.
+        """
+    def with_reveal_shape(self, point:tuple[builtins.int, builtins.int], shape:RevealShape) -> Map:
+        r"""
+        Sets the area revealed around a special point or an unlocked grid point, instead of the circle which
+        depends on the grid size. A watchtower can reveal far more than a campfire.
+        
+        Parameters
+        ----------
+        point : Tuple[int, int]
+            A special point of the map, or a grid point as in :func:`Map.unlocked`.
+        shape : RevealShape
+            The shape revealed around the point.
+        
+        Returns
+        -------
+        Map
+            The map with the reveal shape.
+        
+        Raises
+        ------
+        ValueError
+            If an ellipse has a radius of 0, a polygon has less than 3 points, or the shape
+            extends further than the diagonal of the map.
+        """
+    def with_region(self, name:builtins.str, points:typing.Sequence[tuple[builtins.int, builtins.int]]) -> Map:
+        r"""
+        Defines a named region, like a kingdom or a crypt, which can be revealed at once with :func:`Map.unlock_region`.
+        Defining a region with an existing name replaces it.
+        
+        Parameters
+        ----------
+        name : str
+            The name of the region.
+        points : List[Tuple[int, int]]
+            The corners of the polygon of the region.
+        
+        Returns
+        -------
+        Map
+            The map with the region.
+        
+        Raises
+        ------
+        OutOfBoundsError
+            If a point of the region is outside of the map.
+        ValueError
+            If the region has less than 3 points.
+        """
+    def unlock_region(self, name:builtins.str) -> builtins.bool:
+        r"""
+        Reveals everything inside a region defined with :func:`Map.with_region`.
+        
+        Parameters
+        ----------
+        name : str
+            The name of the region.
+        
+        Returns
+        -------
+        bool
+            True if the region was unlocked, False if it already was unlocked.
+        
+        Raises
+        ------
+        ValueError
+            If there is no region with the name.
+        """
+    def lock_region(self, name:builtins.str) -> builtins.bool:
+        r"""
+        Hides a region again which was unlocked with :func:`Map.unlock_region`.
+        Grid points unlocked inside the region stay revealed.
+        
+        Parameters
+        ----------
+        name : str
+            The name of the region.
+        
+        Returns
+        -------
+        bool
+            True if the region was locked, False if it was not unlocked.
+        
+        Raises
+        ------
+        ValueError
+            If there is no region with the name.
+        """
     def clear_extras(self) -> Map:
         r"""
         Clears all internal variables that may be set to true to start with a clean slate
         """
-    def unlock_point_from_coordinates(self, x:builtins.int, y:builtins.int) -> builtins.bool:
+    def unlock_point_from_coordinates(self, x:builtins.int, y:builtins.int, shape:typing.Optional[RevealShape]=None) -> builtins.bool:
         r"""
         Takes in a coordinate, if it is close to an "unlocked" grid point it will unlock it and return true, if the point is already unlocked it will return false
         
@@ -98,15 +502,71 @@ class Map:
             The x coordinate of the point to unlock.
         y : int
             The y coordinate of the point to unlock.
+        shape : Optional[RevealShape]
+            The shape revealed around the point. Defaults to a circle which depends on the grid size.
         
         Returns
         -------
         bool
             True if the point was unlocked, False otherwise (already unlocked).
+        
+        Raises
+        ------
+        ValueError
+            If an ellipse has a radius of 0, a polygon has less than 3 points, or the shape
+            extends further than the diagonal of the map.
+        """
+    def lock_point_from_coordinates(self, x:builtins.int, y:builtins.int) -> builtins.bool:
+        r"""
+        Takes in a coordinate and hides the grid point closest to it again, if it is unlocked.
+        
+        Parameters
+        ----------
+        x : int
+            The x coordinate of the point to lock.
+        y : int
+            The y coordinate of the point to lock.
+        
+        Returns
+        -------
+        bool
+            True if the point was locked, False otherwise (not unlocked).
         """
-    def draw_path(self, travel:Travel, percentage:builtins.float, line_width:builtins.int, path_type:PathStyle=..., display_style:PathDisplayType=PathDisplayType.BelowMask, progress_display_type:PathProgressDisplayType=PathProgressDisplayType.Travelled) -> builtins.list[builtins.int]:
+    def with_decay(self, ticks:typing.Optional[builtins.int]) -> Map:
+        r"""
+        Makes unlocked points fade back into the fog once they have not been visited for a number of ticks,
+        like after a storm or a memory loss curse. The clock is advanced with :func:`Map.tick`.
+        
+        Parameters
+        ----------
+        ticks : Optional[int]
+            After how many ticks without a visit points are hidden again. None turns decay off, which is the default.
+        
+        Returns
+        -------
+        Map
+            The map with decay.
+        """
+    def tick(self, ticks:builtins.int=1) -> builtins.list[tuple[builtins.int, builtins.int]]:
+        r"""
+        Advances the clock of the map, for example once per turn or once per move.
+        Unlocked points which were not visited for the ticks set with :func:`Map.with_decay` are hidden again.
+        Unlocking a point, also when it is already unlocked, counts as a visit.
+        
+        Parameters
+        ----------
+        ticks : int
+            How many ticks passed. Defaults to 1.
+        
+        Returns
+        -------
+        List[Tuple[int, int]]
+            The points which were hidden again.
+        """
+    def draw_path(self, travel:Travel, percentage:builtins.float, line_width:builtins.int, path_type:PathStyle=..., display_style:PathDisplayType=PathDisplayType.BelowMask, progress_display_type:PathProgressDisplayType=PathProgressDisplayType.Travelled) -> bytes:
         r"""
         Draws the path from :func:`Travel.computed_path` on the image.
+        The Python GIL is released while drawing.
         
         Parameters
         ----------
@@ -123,47 +583,108 @@ class Map:
         
         Returns
         -------
-        List[int]
+        bytes
             The bytes of the image with the path drawn.
+        
+        Raises
+        ------
+        InvalidStyleError
+            If the line width is smaller than 1 (2 for paths with an outline) or larger than the grid size.
         """
-    def full_image(self) -> builtins.list[builtins.int]:
+    def full_image(self) -> bytes:
         r"""
         Returns the full image. If specified, draws the grid, obstacles, and dots.
         
         Returns
         -------
-        List[int]
+        bytes
            The bytes of the image with the grid, obstacles, and dots drawn.
         """
-    def masked_image(self) -> builtins.list[builtins.int]:
+    def masked_image(self) -> bytes:
         r"""
         Returns the masked image. If specified, draws the grid, obstacles, and dots.
         
         Returns
         -------
-        List[int]
+        bytes
           The bytes of the image with the grid, obstacles, and dots drawn.
         """
-    def get_bits(self) -> builtins.list[builtins.int]:
+    def get_bits(self) -> bytes:
         r"""
         The main method to get the image bytes.
         Respects the map type and draws the grid, obstacles, and dots if specified.
+        The Python GIL is released while drawing, so several maps can be drawn in parallel threads.
         
         Returns
         -------
-        List[int]
+        bytes
           The bytes of the image with the grid, obstacles, and dots drawn.
         """
+    def to_png_bytes(self, bytes:typing.Optional[typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]]=None) -> bytes:
+        r"""
+        Encodes an image of the map as PNG. Only available if rpg_map was built with the ``image`` feature.
+        
+        Parameters
+        ----------
+        bytes : Optional[bytes]
+            The bytes to encode, for example the result of :func:`Map.draw_path` or :func:`Map.draw_background`.
+            Defaults to the result of :func:`Map.get_bits`.
+        
+        Returns
+        -------
+        bytes
+            The PNG file.
+        """
+    def save_png(self, path:builtins.str | os.PathLike | pathlib.Path, bytes:typing.Optional[typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]]=None) -> None:
+        r"""
+        Saves an image of the map as a PNG file. Only available if rpg_map was built with the ``image`` feature.
+        
+        Parameters
+        ----------
+        path : str
+            The path to save the file at.
+        bytes : Optional[bytes]
+            The bytes to save, for example the result of :func:`Map.draw_path` or :func:`Map.draw_background`.
+            Defaults to the result of :func:`Map.get_bits`.
+        """
+
+class MovementRange:
+    r"""
+    All points which can be reached from an origin within a movement budget,
+    as returned by :func:`Travel.movement_range`.
+    
+    Attributes
+    ---------
+    origin : tuple[int, int]
+       The point the movement starts from.
+    budget : int
+       The maximum movement cost.
+    distances : dict[tuple[int, int], int]
+       The movement cost to reach every reachable point, including the origin.
+    """
+    origin: tuple[builtins.int, builtins.int]
+    budget: builtins.int
+    distances: builtins.dict[tuple[builtins.int, builtins.int], builtins.int]
 
 class PathPoint:
     x: builtins.int
     y: builtins.int
+    def __new__(cls, x:builtins.int, y:builtins.int) -> PathPoint: ...
+    def __reduce__(self) -> tuple[typing.Any, tuple[builtins.int, builtins.int]]: ...
+    def __copy__(self) -> PathPoint: ...
+    def __deepcopy__(self, _memo:typing.Any) -> PathPoint: ...
 
 class Travel:
     r"""
     A class representing a travel from one point to another on a map.
     This class contains the shortest path from point A to point B on the map.
-    It uses the A* algorithm to find the path.
+    It uses the A* algorithm to find the path. If the map has a cost layer
+    (see :func:`Map.with_cost_map`) the path is the cheapest instead of the shortest one.
+    
+    The grid used for pathfinding is built once per map and reused by every
+    following travel on it (or a copy of it) until the map changes.
+    The path is computed without holding the Python GIL, so several travels
+    can be computed in parallel threads.
     
     Parameters
     ----------
@@ -173,16 +694,71 @@ class Travel:
        The current location of the traveler. Given as a tuple of (x, y) coordinates.
     destination : tuple[int, int]
        The destination of the traveler. Given as a tuple of (x, y) coordinates.
+    smooth : bool
+       If true, the path is straightened into natural looking lines at any angle instead of
+       only horizontal, vertical and diagonal steps. It still never crosses an obstacle.
+    snap_destination : bool
+       If true, a destination which is an obstacle or cannot be reached is moved to the
+       closest point which can be reached instead of raising an error.
+    hierarchical : bool
+       If true, the path is found on a coarse graph of the map first and only refined
+       where the path goes (HPA*). This is much faster and uses less memory on very
+       large maps, but the path can be slightly longer than the shortest one. The graph is
+       built on first use and cached like the pathfinding grid. If there is no path or the
+       destination has to be snapped, the whole map is still checked like without it.
         
     Attributes
     ---------
     computed_path : list[PathPoint]
        The computed path from the current location to the destination.
+    legs : list[tuple[int, int]]
+       The start and end index in `computed_path` of every leg of the travel.
+       A travel from a current location to a destination has a single leg.
+    snap_distance : Optional[float]
+       How far in pixels the destination was moved to reach it, or None if it was not moved.
+    
+    Raises
+    ------
+    OutOfBoundsError
+       If the current location or destination is outside of the map.
+    BlockedEndpointError
+       If the current location or destination is an obstacle.
+    NoPathError
+       If the destination cannot be reached from the current location.
     """
     computed_path: builtins.list[PathPoint]
-    def __new__(cls, map:Map, current_location:tuple[builtins.int, builtins.int], destination:tuple[builtins.int, builtins.int]) -> Travel: ...
+    legs: builtins.list[tuple[builtins.int, builtins.int]]
+    snap_distance: typing.Optional[builtins.float]
+    def __new__(cls, map:Map, current_location:tuple[builtins.int, builtins.int], destination:tuple[builtins.int, builtins.int], smooth:builtins.bool=False, snap_destination:builtins.bool=False, hierarchical:builtins.bool=False) -> Travel: ...
+    @staticmethod
+    def from_waypoints(map:Map, waypoints:typing.Sequence[tuple[builtins.int, builtins.int]], smooth:builtins.bool=False, snap_destination:builtins.bool=False, hierarchical:builtins.bool=False) -> Travel:
+        r"""
+        Creates a travel which visits all given waypoints in order.
+        The paths between two consecutive waypoints (legs) are stitched
+        together into one :attr:`Travel.computed_path`.
+        
+        Parameters
+        ----------
+        map : Map
+           The map to travel on.
+        waypoints : list[tuple[int, int]]
+           The points to visit in order, starting with the current location of the traveler.
+           Requires at least two points.
+        smooth : bool
+           If true, the path of every leg is straightened. See :class:`Travel`.
+        snap_destination : bool
+           If true, the last waypoint is moved to the closest reachable point if it cannot be reached.
+           See :class:`Travel`.
+        hierarchical : bool
+           If true, every leg is found with hierarchical pathfinding. See :class:`Travel`.
+        
+        Returns
+        -------
+        Travel
+           The travel along all waypoints.
+        """
     @staticmethod
-    def dbg_map(map:Map) -> builtins.list[builtins.int]:
+    def dbg_map(map:Map) -> bytes:
         r"""
         Displays the map in a black and white view where white are the
         obstacles and black are the free spaces. This is to debug if
@@ -196,8 +772,101 @@ class Travel:
         
         Returns
         -------
-        list[int]
-          A list of bytes representing the black and white view of the map.
+        bytes
+          The bytes of the black and white view of the map.
+        """
+    def to_binary(self) -> bytes:
+        r"""
+        Serializes the travel, including its map and path, to a compact binary format.
+        It can be restored with :func:`Travel.from_binary`. This is also used to pickle it.
+        
+        Returns
+        -------
+        bytes
+           The state of the travel.
+        """
+    @staticmethod
+    def from_binary(data:typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]) -> Travel:
+        r"""
+        Restores a travel serialized with :func:`Travel.to_binary` without computing the path again.
+        
+        Parameters
+        ---------
+        data : bytes
+          The bytes created by :func:`Travel.to_binary`.
+        
+        Returns
+        -------
+        Travel
+          The restored travel.
+        """
+    def __reduce__(self) -> tuple[typing.Any, tuple]:
+        r"""
+        Pickles the travel, see :func:`Travel.to_binary`
+        """
+    def __copy__(self) -> Travel: ...
+    def __deepcopy__(self, _memo:typing.Any) -> Travel: ...
+    @staticmethod
+    def is_reachable(map:Map, current_location:tuple[builtins.int, builtins.int], destination:tuple[builtins.int, builtins.int]) -> builtins.bool:
+        r"""
+        Checks if there is a path between two points without computing it.
+        This is much faster than creating a :class:`Travel`, especially if there is no path.
+        
+        Parameters
+        ---------
+        map : Map
+          The map to check on.
+        current_location : tuple[int, int]
+          The point to start from.
+        destination : tuple[int, int]
+          The point to reach.
+        
+        Returns
+        -------
+        bool
+          True if the destination can be reached, False if there is no path or one of the points is an obstacle.
+        """
+    @staticmethod
+    def region_of(map:Map, point:tuple[builtins.int, builtins.int]) -> typing.Optional[builtins.int]:
+        r"""
+        Finds the region a point is in. Regions are areas of the map which are
+        separated from each other by obstacles, so there is a path between two
+        points if and only if they are in the same region.
+        
+        Parameters
+        ---------
+        map : Map
+          The map to check on.
+        point : tuple[int, int]
+          The point to find the region of.
+        
+        Returns
+        -------
+        Optional[int]
+          The id of the region, or None if the point is an obstacle.
+        """
+    @staticmethod
+    def movement_range(map:Map, start:tuple[builtins.int, builtins.int], budget:builtins.int) -> MovementRange:
+        r"""
+        Finds every point which can be reached from a start point within a movement budget,
+        for example to highlight how far a unit can move in one turn.
+        Uses the same rules as the pathfinding: every step, including diagonal ones, costs 1,
+        or the cost of the terrain if the map has a cost layer (see :func:`Map.with_cost_map`).
+        The result can be drawn with :func:`Map.with_movement_range`.
+        
+        Parameters
+        ---------
+        map : Map
+          The map to move on.
+        start : tuple[int, int]
+          The point to start from.
+        budget : int
+          The maximum total movement cost.
+        
+        Returns
+        -------
+        MovementRange
+          All reachable points with the cost to reach them.
         """
 
 class MapType(Enum):
@@ -212,10 +881,14 @@ class MapType(Enum):
        The map reveals all the unlocked points.
     Full
        The map reveals all the points.
+    Explored
+       The map reveals the current position, which is the last unlocked point, and dims all
+       other unlocked points. See :func:`Map.with_explored_fog`.
     """
     Hidden = ...
     Limited = ...
     Full = ...
+    Explored = ...
 
 class PathDisplayType(Enum):
     r"""
@@ -271,3 +944,33 @@ class PathStyle(Enum):
     SolidWithOutline = ...
     DottedWithOutline = ...
 
+class RevealShape(Enum):
+    r"""
+    The shape of the area revealed around a point. All sizes are in pixels.
+    
+    Attributes
+    ---------
+    Circle
+      A circle with a radius.
+    Square
+      A square which extends the given distance from the point to each side.
+    Ellipse
+      An ellipse with a horizontal and a vertical radius.
+    Polygon
+      A polygon with at least 3 corners, given as offsets from the point.
+    """
+    Circle = ...
+    Square = ...
+    Ellipse = ...
+    Polygon = ...
+
+class BlockedEndpointError(ValueError): ...
+
+class InvalidStyleError(ValueError): ...
+
+class NoPathError(ValueError): ...
+
+class OutOfBoundsError(ValueError): ...
+
+class SizeMismatchError(ValueError): ...
+
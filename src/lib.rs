@@ -1,23 +1,22 @@
-use pyo3::prelude::*;
+//! Reveal, draw and find paths on RPG maps.
+//!
+//! The Rust API does not depend on Python. The Python bindings are built with the `python` feature.
 #[cfg(feature = "stubgen")]
 use pyo3_stub_gen::define_stub_info_gatherer;
 
+#[cfg(feature = "python")]
+mod python;
 mod structs;
 #[cfg(test)]
 mod tests;
 
-#[pymodule]
-fn rpg_map(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    m.add_class::<structs::map::Map>()?;
-    m.add_class::<structs::map::MapType>()?;
-    m.add_class::<structs::map::PathStyle>()?;
-    m.add_class::<structs::travel::Travel>()?;
-    m.add_class::<structs::map::PathDisplayType>()?;
-    m.add_class::<structs::path::PathPoint>()?;
-    m.add_class::<structs::map::PathProgressDisplayType>()?;
-
-    Ok(())
-}
+pub use structs::error::{Result, RpgMapError};
+pub use structs::map::{
+    Fog, Map, MapType, PathDisplayType, PathProgressDisplayType, PathStyle, RevealShape,
+};
+pub use structs::path::PathPoint;
+pub use structs::state::{MapState, TravelState};
+pub use structs::travel::{MovementRange, Travel, TravelOptions};
 
 #[cfg(feature = "stubgen")]
 define_stub_info_gatherer!(stub_info);
@@ -0,0 +1,40 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyList};
+
+/// Image bytes passed in from Python.
+///
+/// `bytes` and `bytearray` are copied in a single step, `memoryview`s, NumPy arrays
+/// and every other object supporting the buffer protocol are read through `bytes(...)`.
+/// A list of ints is still accepted, but converting it is much slower.
+pub struct ImageBytes(pub Vec<u8>);
+
+impl<'py> FromPyObject<'py> for ImageBytes {
+    fn extract_bound(object: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(bytes) = object.downcast::<PyBytes>() {
+            return Ok(ImageBytes(bytes.as_bytes().to_vec()));
+        }
+        if let Ok(bytes) = object.downcast::<PyByteArray>() {
+            return Ok(ImageBytes(bytes.to_vec()));
+        }
+        if object.is_instance_of::<PyList>() {
+            return Ok(ImageBytes(object.extract()?));
+        }
+        // The buffer protocol is not part of the limited API before Python 3.11
+        let bytes = object
+            .py()
+            .get_type::<PyBytes>()
+            .call1((object,))?
+            .downcast_into::<PyBytes>()?;
+        Ok(ImageBytes(bytes.as_bytes().to_vec()))
+    }
+}
+
+#[cfg(feature = "stubgen")]
+impl pyo3_stub_gen::PyStubType for ImageBytes {
+    fn type_output() -> pyo3_stub_gen::TypeInfo {
+        pyo3_stub_gen::TypeInfo {
+            name: "typing.Union[builtins.bytes, builtins.bytearray, builtins.memoryview, typing.Sequence[builtins.int]]".to_string(),
+            import: ["builtins".into(), "typing".into()].into(),
+        }
+    }
+}
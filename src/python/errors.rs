@@ -0,0 +1,113 @@
+use crate::structs::error::RpgMapError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::IntoPyObjectExt;
+
+/// Creates an exception which subclasses ``ValueError``, so code catching
+/// ``ValueError`` keeps working. Stubs are only generated for it with the `stubgen` feature.
+macro_rules! exception {
+    ($name: ident, $doc: expr) => {
+        #[cfg(feature = "stubgen")]
+        pyo3_stub_gen::create_exception!(rpg_map, $name, PyValueError, $doc);
+        #[cfg(not(feature = "stubgen"))]
+        pyo3::create_exception!(rpg_map, $name, PyValueError, $doc);
+    };
+}
+
+exception!(
+    OutOfBoundsError,
+    "A point is outside of the map.\n\nAttributes\n----------\npoint : tuple[int, int]\n    The point outside of the map.\nwidth : int\n    The width of the map.\nheight : int\n    The height of the map."
+);
+exception!(
+    BlockedEndpointError,
+    "The start or destination of a path or movement is an obstacle.\n\nAttributes\n----------\npoint : tuple[int, int]\n    The point which is an obstacle."
+);
+exception!(
+    NoPathError,
+    "There is no path between two points.\n\nAttributes\n----------\nstart : tuple[int, int]\n    The point the path starts at.\ndestination : tuple[int, int]\n    The point which cannot be reached."
+);
+exception!(
+    InvalidStyleError,
+    "A path cannot be drawn with the line width.\n\nAttributes\n----------\nline_width : int\n    The line width passed in.\nmin_width : int\n    The smallest line width of the path style, 2 for outlines and 1 otherwise.\nmax_width : int\n    The largest line width, the grid size of the map."
+);
+exception!(
+    SizeMismatchError,
+    "An image does not have the same size as the map.\n\nAttributes\n----------\nexpected : int\n    The number of bytes of the map.\nactual : int\n    The number of bytes of the image."
+);
+
+/// Registers the exceptions in the module
+pub fn add_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("OutOfBoundsError", py.get_type::<OutOfBoundsError>())?;
+    m.add(
+        "BlockedEndpointError",
+        py.get_type::<BlockedEndpointError>(),
+    )?;
+    m.add("NoPathError", py.get_type::<NoPathError>())?;
+    m.add("InvalidStyleError", py.get_type::<InvalidStyleError>())?;
+    m.add("SizeMismatchError", py.get_type::<SizeMismatchError>())?;
+    Ok(())
+}
+
+impl From<RpgMapError> for PyErr {
+    fn from(error: RpgMapError) -> Self {
+        Python::with_gil(|py| to_py_err(py, error).unwrap_or_else(|error| error))
+    }
+}
+
+/// Creates the exception matching the error, with the values of the error as attributes
+fn to_py_err(py: Python<'_>, error: RpgMapError) -> PyResult<PyErr> {
+    let message = error.to_string();
+    let (exception, attributes) = match error {
+        RpgMapError::OutOfBounds {
+            point,
+            width,
+            height,
+        } => (
+            OutOfBoundsError::new_err(message),
+            vec![
+                ("point", point.into_py_any(py)?),
+                ("width", width.into_py_any(py)?),
+                ("height", height.into_py_any(py)?),
+            ],
+        ),
+        RpgMapError::BlockedEndpoint { point } => (
+            BlockedEndpointError::new_err(message),
+            vec![("point", point.into_py_any(py)?)],
+        ),
+        RpgMapError::NoPath { start, destination } => (
+            NoPathError::new_err(message),
+            vec![
+                ("start", start.into_py_any(py)?),
+                ("destination", destination.into_py_any(py)?),
+            ],
+        ),
+        RpgMapError::InvalidStyle {
+            line_width,
+            min,
+            max,
+        } => (
+            InvalidStyleError::new_err(message),
+            vec![
+                ("line_width", line_width.into_py_any(py)?),
+                ("min_width", min.into_py_any(py)?),
+                ("max_width", max.into_py_any(py)?),
+            ],
+        ),
+        RpgMapError::SizeMismatch {
+            expected, actual, ..
+        } => (
+            SizeMismatchError::new_err(message),
+            vec![
+                ("expected", expected.into_py_any(py)?),
+                ("actual", actual.into_py_any(py)?),
+            ],
+        ),
+        RpgMapError::Io(error) => return Ok(error.into()),
+        _ => return Ok(PyValueError::new_err(message)),
+    };
+    for (name, value) in attributes {
+        exception.value(py).setattr(name, value)?;
+    }
+    Ok(exception)
+}
@@ -0,0 +1,905 @@
+use crate::python::buffer::ImageBytes;
+use crate::structs::map::{
+    Map, MapType, PathDisplayType, PathProgressDisplayType, PathStyle, RevealShape,
+};
+use crate::structs::travel::{MovementRange, Travel};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyTuple};
+use std::collections::BTreeMap;
+use workaround::stubgen;
+
+#[stubgen]
+#[pymethods]
+impl Map {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        bytes,
+        width,
+        height,
+        grid_size,
+        map_type = MapType::Full,
+        unlocked = vec![],
+        special_points = vec![],
+        obstacles = vec![]
+    ))]
+    fn py_new(
+        bytes: ImageBytes,
+        width: u32,
+        height: u32,
+        grid_size: u32,
+        map_type: MapType,
+        unlocked: Vec<(u32, u32)>,
+        special_points: Vec<(u32, u32)>,
+        obstacles: Vec<Vec<(u32, u32)>>,
+    ) -> PyResult<Self> {
+        Ok(Map::new(
+            bytes.0,
+            width,
+            height,
+            grid_size,
+            map_type,
+            unlocked,
+            special_points,
+            obstacles,
+        )?)
+    }
+
+    #[getter]
+    fn get_width(&self) -> u32 {
+        self.width()
+    }
+
+    #[getter]
+    fn get_height(&self) -> u32 {
+        self.height()
+    }
+
+    #[getter]
+    fn get_unlocked(&self) -> Vec<(u32, u32)> {
+        self.unlocked().to_vec()
+    }
+
+    /// The names of the regions which are unlocked.
+    #[getter]
+    fn get_unlocked_regions(&self) -> Vec<String> {
+        self.unlocked_regions().map(str::to_string).collect()
+    }
+
+    /// The grid point which was unlocked last. On an explored map this is the area which is currently visible.
+    #[getter]
+    fn get_current_position(&self) -> Option<(u32, u32)> {
+        self.current_position()
+    }
+
+    /// Creates a map from a PNG or WebP file. Only available if rpg_map was built with the ``image`` feature.
+    ///
+    /// Parameters
+    /// ----------
+    /// path : str
+    ///     The path of the image file.
+    /// grid_size : int
+    ///     The size of a single box in the grid, see :class:`Map`.
+    /// map_type : MapType
+    ///     The type of the map. Can be Hidden, Limited or Full.
+    /// unlocked : List[Tuple[int, int]]
+    ///     The points that are unlocked on the map.
+    /// special_points : List[Tuple[int, int]]
+    ///     The special points on the map.
+    /// obstacles : List[List[Tuple[int, int]]]
+    ///     The obstacles on the map.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the size and bytes of the image.
+    #[cfg(feature = "image")]
+    #[staticmethod]
+    #[pyo3(signature = (
+        path,
+        grid_size,
+        map_type = MapType::Full,
+        unlocked = vec![],
+        special_points = vec![],
+        obstacles = vec![]
+    ))]
+    #[pyo3(name = "from_file")]
+    fn py_from_file(
+        py: Python<'_>,
+        path: std::path::PathBuf,
+        grid_size: u32,
+        map_type: MapType,
+        unlocked: Vec<(u32, u32)>,
+        special_points: Vec<(u32, u32)>,
+        obstacles: Vec<Vec<(u32, u32)>>,
+    ) -> PyResult<Map> {
+        let map = py.allow_threads(|| {
+            Map::from_file(
+                &path,
+                grid_size,
+                map_type,
+                unlocked,
+                special_points,
+                obstacles,
+            )
+        })?;
+        Ok(map)
+    }
+
+    /// Creates a map from the contents of a PNG (or WebP) file. Only available if rpg_map was built with the ``image`` feature.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     The encoded image.
+    /// grid_size : int
+    ///     The size of a single box in the grid, see :class:`Map`.
+    /// map_type : MapType
+    ///     The type of the map. Can be Hidden, Limited or Full.
+    /// unlocked : List[Tuple[int, int]]
+    ///     The points that are unlocked on the map.
+    /// special_points : List[Tuple[int, int]]
+    ///     The special points on the map.
+    /// obstacles : List[List[Tuple[int, int]]]
+    ///     The obstacles on the map.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the size and bytes of the image.
+    #[cfg(feature = "image")]
+    #[staticmethod]
+    #[pyo3(signature = (
+        data,
+        grid_size,
+        map_type = MapType::Full,
+        unlocked = vec![],
+        special_points = vec![],
+        obstacles = vec![]
+    ))]
+    #[pyo3(name = "from_png_bytes")]
+    fn py_from_png_bytes(
+        py: Python<'_>,
+        data: ImageBytes,
+        grid_size: u32,
+        map_type: MapType,
+        unlocked: Vec<(u32, u32)>,
+        special_points: Vec<(u32, u32)>,
+        obstacles: Vec<Vec<(u32, u32)>>,
+    ) -> PyResult<Map> {
+        let map = py.allow_threads(|| {
+            Map::from_png_bytes(
+                &data.0,
+                grid_size,
+                map_type,
+                unlocked,
+                special_points,
+                obstacles,
+            )
+        })?;
+        Ok(map)
+    }
+
+    /// Serializes the full state of the map, including the unlocked points, obstacles,
+    /// dots and draw settings, to JSON. It can be restored with :func:`Map.from_json`.
+    ///
+    /// Parameters
+    /// ----------
+    /// include_bytes : bool
+    ///     If false, the bytes of the image are left out to keep the result small. Only a hash of them is stored,
+    ///     so they have to be passed to :func:`Map.from_json` again.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    ///     The state of the map as JSON.
+    #[pyo3(name = "to_json", signature = (include_bytes = true))]
+    fn py_to_json(&self, include_bytes: bool) -> PyResult<String> {
+        Ok(self.to_json(include_bytes)?)
+    }
+
+    /// Restores a map serialized with :func:`Map.to_json`.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : str
+    ///     The JSON created by :func:`Map.to_json`.
+    /// bytes : Optional[bytes]
+    ///     The bytes of the image. Required if the map was serialized without them.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The restored map.
+    #[staticmethod]
+    #[pyo3(name = "from_json", signature = (data, bytes = None))]
+    fn py_from_json(data: &str, bytes: Option<ImageBytes>) -> PyResult<Map> {
+        Ok(Map::from_json(data, bytes.map(|bytes| bytes.0))?)
+    }
+
+    /// Serializes the full state of the map to a compact binary format.
+    /// It can be restored with :func:`Map.from_binary`.
+    ///
+    /// Parameters
+    /// ----------
+    /// include_bytes : bool
+    ///     If false, the bytes of the image are left out, see :func:`Map.to_json`.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///     The state of the map.
+    #[pyo3(name = "to_binary", signature = (include_bytes = true))]
+    fn py_to_binary<'py>(
+        &self,
+        py: Python<'py>,
+        include_bytes: bool,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.to_binary(include_bytes)?))
+    }
+
+    /// Restores a map serialized with :func:`Map.to_binary`.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     The bytes created by :func:`Map.to_binary`.
+    /// bytes : Optional[bytes]
+    ///     The bytes of the image. Required if the map was serialized without them.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The restored map.
+    #[staticmethod]
+    #[pyo3(name = "from_binary", signature = (data, bytes = None))]
+    fn py_from_binary(data: ImageBytes, bytes: Option<ImageBytes>) -> PyResult<Map> {
+        Ok(Map::from_binary(&data.0, bytes.map(|bytes| bytes.0))?)
+    }
+
+    /// Pickles the map with its full state, see :func:`Map.to_binary`
+    pub fn __reduce__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let from_binary = py.get_type::<Map>().getattr("from_binary")?;
+        Ok((
+            from_binary,
+            PyTuple::new(py, [self.py_to_binary(py, true)?])?,
+        ))
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Draws the background image at every transparent pixel
+    /// if the background is set
+    ///
+    /// Parameters
+    /// ----------
+    /// bytes : bytes
+    ///     The bytes of the image.
+    /// background : bytes
+    ///     The bytes of the background of the image.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///     The bytes of the image with the background.
+    ///
+    /// Raises
+    /// ------
+    /// SizeMismatchError
+    ///     If the background does not have the same size as the image.
+    #[staticmethod]
+    #[pyo3(name = "draw_background")]
+    fn py_draw_background<'py>(
+        py: Python<'py>,
+        bytes: ImageBytes,
+        background: ImageBytes,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let image = py.allow_threads(|| Map::draw_background(bytes.0, background.0))?;
+        Ok(PyBytes::new(py, &image))
+    }
+
+    /// Adds a dot do be drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
+    ///
+    /// Parameters
+    /// ----------
+    /// x : int
+    ///     The x coordinate of the dot.
+    /// y : int
+    ///     The y coordinate of the dot.
+    /// color : Tuple[int, int, int, int]
+    ///     The color of the dot.
+    /// radius : int
+    ///     The radius of the dot.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the dot.
+    ///
+    #[pyo3(name = "with_dot")]
+    fn py_with_dot(
+        mut slf: PyRefMut<'_, Self>,
+        x: u32,
+        y: u32,
+        color: [u8; 4],
+        radius: u32,
+    ) -> PyRefMut<'_, Self> {
+        slf.with_dot(x, y, color, radius);
+        slf
+    }
+
+    /// Tints all points of a movement range to be drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
+    ///
+    /// Parameters
+    /// ----------
+    /// movement_range : MovementRange
+    ///     The movement range to draw, see :func:`Travel.movement_range`.
+    /// color : Tuple[int, int, int, int]
+    ///     The color of the tint. Its alpha value is the opacity of the tint.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the movement range.
+    #[pyo3(name = "with_movement_range")]
+    fn py_with_movement_range(
+        mut slf: PyRefMut<'_, Self>,
+        movement_range: MovementRange,
+        color: [u8; 4],
+    ) -> PyRefMut<'_, Self> {
+        slf.with_movement_range(&movement_range, color);
+        slf
+    }
+
+    /// If called, a grid is drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
+    #[pyo3(name = "with_grid")]
+    fn py_with_grid(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.with_grid();
+        slf
+    }
+
+    /// If called, the obstacles are drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
+    #[pyo3(name = "with_obstacles")]
+    fn py_with_obstacles(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.with_obstacles();
+        slf
+    }
+
+    /// Sets a terrain cost layer which :class:`Travel` uses to weigh each step of the path.
+    /// Paths prefer cheap terrain (like roads) and avoid expensive terrain (like swamps or mountains)
+    /// without treating it as an obstacle.
+    ///
+    /// Parameters
+    /// ----------
+    /// cost_map : bytes
+    ///     The bytes of an image with the same size as the map. The brightness of each pixel is its movement cost,
+    ///     where black is the cheapest (a cost of 1) and white the most expensive (a cost of 255).
+    ///     Fully transparent pixels have a cost of 1.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the cost layer.
+    ///
+    /// Raises
+    /// ------
+    /// SizeMismatchError
+    ///     If the cost map does not have the same size as the map.
+    #[pyo3(name = "with_cost_map")]
+    fn py_with_cost_map(
+        mut slf: PyRefMut<'_, Self>,
+        cost_map: ImageBytes,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_cost_map(cost_map.0)?;
+        Ok(slf)
+    }
+
+    /// Sets how much clearance paths keep from obstacles and the edges of the map.
+    /// Use a small buffer to keep narrow passes open or a large one for travellers who need more room.
+    ///
+    /// Parameters
+    /// ----------
+    /// edges : int
+    ///     The clearance in pixels from transparent pixels, i.e. the edges of the map. Defaults to 5.
+    /// obstacles : Optional[int]
+    ///     The clearance in pixels from the polygon obstacles. Defaults to the clearance of the edges.
+    /// overrides : Dict[int, int]
+    ///     The clearance of single obstacles, keyed by their index in the obstacles of the map.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the buffers.
+    #[pyo3(name = "with_buffer", signature = (edges, obstacles = None, overrides = BTreeMap::new()))]
+    fn py_with_buffer(
+        mut slf: PyRefMut<'_, Self>,
+        edges: u32,
+        obstacles: Option<u32>,
+        overrides: BTreeMap<usize, u32>,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_buffer(edges, obstacles, overrides)?;
+        Ok(slf)
+    }
+
+    /// Fades the revealed areas smoothly into the fog instead of cutting them out with a hard edge.
+    ///
+    /// Parameters
+    /// ----------
+    /// width : int
+    ///     The width in pixels of the fade at the edge of every revealed circle. Defaults to 0, a hard edge.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the feathered fog.
+    #[pyo3(name = "with_fog_feather")]
+    fn py_with_fog_feather(mut slf: PyRefMut<'_, Self>, width: u32) -> PyRefMut<'_, Self> {
+        slf.with_fog_feather(width);
+        slf
+    }
+
+    /// Covers the hidden areas of the map with a color instead of black.
+    ///
+    /// Parameters
+    /// ----------
+    /// color : Tuple[int, int, int, int]
+    ///     The RGBA color of the fog. With an alpha below 255 the map shows through the fog.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the colored fog.
+    #[pyo3(name = "with_fog_color")]
+    fn py_with_fog_color(mut slf: PyRefMut<'_, Self>, color: [u8; 4]) -> PyRefMut<'_, Self> {
+        slf.with_fog_color(color);
+        slf
+    }
+
+    /// Covers the hidden areas of the map with a texture, like old parchment, which is repeated over the map.
+    ///
+    /// Parameters
+    /// ----------
+    /// texture : bytes
+    ///     The RGBA bytes of the texture. Transparent pixels of the texture let the map show through.
+    /// width : int
+    ///     The width of the texture.
+    /// height : int
+    ///     The height of the texture.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the textured fog.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If the texture does not have ``width * height * 4`` bytes.
+    #[pyo3(name = "with_fog_texture")]
+    fn py_with_fog_texture(
+        mut slf: PyRefMut<'_, Self>,
+        texture: ImageBytes,
+        width: u32,
+        height: u32,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_fog_texture(texture.0, width, height)?;
+        Ok(slf)
+    }
+
+    /// Shows a darkened and desaturated version of the map in the hidden areas instead of covering them.
+    ///
+    /// Parameters
+    /// ----------
+    /// brightness : float
+    ///     How bright the hidden areas are, from 0 (black) to 1 (unchanged).
+    /// saturation : float
+    ///     How colorful the hidden areas are, from 0 (grayscale) to 1 (unchanged).
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the shaded fog.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If the brightness or saturation is not between 0 and 1.
+    #[pyo3(name = "with_fog_shade")]
+    fn py_with_fog_shade(
+        mut slf: PyRefMut<'_, Self>,
+        brightness: f32,
+        saturation: f32,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_fog_shade(brightness, saturation)?;
+        Ok(slf)
+    }
+
+    /// Sets how much fog covers the explored areas of a map with the ``MapType.Explored`` type
+    /// which are not visible from the current position.
+    ///
+    /// Parameters
+    /// ----------
+    /// opacity : float
+    ///     How much fog covers the explored areas, from 0 (fully visible) to 1 (as hidden as unexplored areas).
+    ///     Defaults to 0.5.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the explored fog.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If the opacity is not between 0 and 1.
+    #[pyo3(name = "with_explored_fog")]
+    fn py_with_explored_fog(
+        mut slf: PyRefMut<'_, Self>,
+        opacity: f32,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_explored_fog(opacity)?;
+        Ok(slf)
+    }
+
+    /// Only reveals what can be seen from the unlocked points instead of a full circle around them.
+    /// Obstacles and transparent pixels block the view, so rooms behind walls stay hidden.
+    ///
+    /// Parameters
+    /// ----------
+    /// enabled : bool
+    ///     Whether the view is blocked by obstacles and transparent pixels. Defaults to True.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with line of sight.
+    #[pyo3(name = "with_line_of_sight", signature = (enabled = true))]
+    fn py_with_line_of_sight(mut slf: PyRefMut<'_, Self>, enabled: bool) -> PyRefMut<'_, Self> {
+        slf.with_line_of_sight(enabled);
+        slf
+    }
+
+    /// Sets the area revealed around a special point or an unlocked grid point, instead of the circle which
+    /// depends on the grid size. A watchtower can reveal far more than a campfire.
+    ///
+    /// Parameters
+    /// ----------
+    /// point : Tuple[int, int]
+    ///     A special point of the map, or a grid point as in :func:`Map.unlocked`.
+    /// shape : RevealShape
+    ///     The shape revealed around the point.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the reveal shape.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If an ellipse has a radius of 0, a polygon has less than 3 points, or the shape
+    ///     extends further than the diagonal of the map.
+    #[pyo3(name = "with_reveal_shape")]
+    fn py_with_reveal_shape(
+        mut slf: PyRefMut<'_, Self>,
+        point: (u32, u32),
+        shape: RevealShape,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_reveal_shape(point, shape)?;
+        Ok(slf)
+    }
+
+    /// Defines a named region, like a kingdom or a crypt, which can be revealed at once with :func:`Map.unlock_region`.
+    /// Defining a region with an existing name replaces it.
+    ///
+    /// Parameters
+    /// ----------
+    /// name : str
+    ///     The name of the region.
+    /// points : List[Tuple[int, int]]
+    ///     The corners of the polygon of the region.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with the region.
+    ///
+    /// Raises
+    /// ------
+    /// OutOfBoundsError
+    ///     If a point of the region is outside of the map.
+    /// ValueError
+    ///     If the region has less than 3 points.
+    #[pyo3(name = "with_region")]
+    fn py_with_region(
+        mut slf: PyRefMut<'_, Self>,
+        name: String,
+        points: Vec<(u32, u32)>,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.with_region(&name, points)?;
+        Ok(slf)
+    }
+
+    /// Reveals everything inside a region defined with :func:`Map.with_region`.
+    ///
+    /// Parameters
+    /// ----------
+    /// name : str
+    ///     The name of the region.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    ///     True if the region was unlocked, False if it already was unlocked.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If there is no region with the name.
+    #[pyo3(name = "unlock_region")]
+    fn py_unlock_region(&mut self, name: &str) -> PyResult<bool> {
+        Ok(self.unlock_region(name)?)
+    }
+
+    /// Hides a region again which was unlocked with :func:`Map.unlock_region`.
+    /// Grid points unlocked inside the region stay revealed.
+    ///
+    /// Parameters
+    /// ----------
+    /// name : str
+    ///     The name of the region.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    ///     True if the region was locked, False if it was not unlocked.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If there is no region with the name.
+    #[pyo3(name = "lock_region")]
+    fn py_lock_region(&mut self, name: &str) -> PyResult<bool> {
+        Ok(self.lock_region(name)?)
+    }
+
+    /// Clears all internal variables that may be set to true to start with a clean slate
+    #[pyo3(name = "clear_extras")]
+    fn py_clear_extras(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.clear_extras();
+        slf
+    }
+
+    /// Takes in a coordinate, if it is close to an "unlocked" grid point it will unlock it and return true, if the point is already unlocked it will return false
+    ///
+    /// Parameters
+    /// ----------
+    /// x : int
+    ///     The x coordinate of the point to unlock.
+    /// y : int
+    ///     The y coordinate of the point to unlock.
+    /// shape : Optional[RevealShape]
+    ///     The shape revealed around the point. Defaults to a circle which depends on the grid size.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    ///     True if the point was unlocked, False otherwise (already unlocked).
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If an ellipse has a radius of 0, a polygon has less than 3 points, or the shape
+    ///     extends further than the diagonal of the map.
+    #[pyo3(name = "unlock_point_from_coordinates", signature = (x, y, shape = None))]
+    fn py_unlock_point_from_coordinates(
+        &mut self,
+        x: u32,
+        y: u32,
+        shape: Option<RevealShape>,
+    ) -> PyResult<bool> {
+        Ok(match shape {
+            Some(shape) => self.unlock_point_with_shape(x, y, shape)?,
+            None => self.unlock_point_from_coordinates(x, y),
+        })
+    }
+
+    /// Takes in a coordinate and hides the grid point closest to it again, if it is unlocked.
+    ///
+    /// Parameters
+    /// ----------
+    /// x : int
+    ///     The x coordinate of the point to lock.
+    /// y : int
+    ///     The y coordinate of the point to lock.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    ///     True if the point was locked, False otherwise (not unlocked).
+    #[pyo3(name = "lock_point_from_coordinates")]
+    fn py_lock_point_from_coordinates(&mut self, x: u32, y: u32) -> bool {
+        self.lock_point_from_coordinates(x, y)
+    }
+
+    /// Makes unlocked points fade back into the fog once they have not been visited for a number of ticks,
+    /// like after a storm or a memory loss curse. The clock is advanced with :func:`Map.tick`.
+    ///
+    /// Parameters
+    /// ----------
+    /// ticks : Optional[int]
+    ///     After how many ticks without a visit points are hidden again. None turns decay off, which is the default.
+    ///
+    /// Returns
+    /// -------
+    /// Map
+    ///     The map with decay.
+    #[pyo3(name = "with_decay")]
+    fn py_with_decay(mut slf: PyRefMut<'_, Self>, ticks: Option<u32>) -> PyRefMut<'_, Self> {
+        slf.with_decay(ticks);
+        slf
+    }
+
+    /// Advances the clock of the map, for example once per turn or once per move.
+    /// Unlocked points which were not visited for the ticks set with :func:`Map.with_decay` are hidden again.
+    /// Unlocking a point, also when it is already unlocked, counts as a visit.
+    ///
+    /// Parameters
+    /// ----------
+    /// ticks : int
+    ///     How many ticks passed. Defaults to 1.
+    ///
+    /// Returns
+    /// -------
+    /// List[Tuple[int, int]]
+    ///     The points which were hidden again.
+    #[pyo3(name = "tick", signature = (ticks = 1))]
+    fn py_tick(&mut self, ticks: u32) -> Vec<(u32, u32)> {
+        self.tick(ticks)
+    }
+
+    /// Draws the path from :func:`Travel.computed_path` on the image.
+    /// The Python GIL is released while drawing.
+    ///
+    /// Parameters
+    /// ----------
+    /// travel : Travel
+    ///     The travel object containing the path to draw.
+    /// percentage : float
+    ///     The percentage of the path to draw. 0.0 to 1.0.
+    /// line_width : int
+    ///     The width of the line to draw in pixels. Note that if the line has an outline the width will be this +2px
+    /// path_type : PathStyle
+    ///     The type of path to draw. Can be Solid, Dotted, SolidWithOutline or DottedWithOutline.
+    /// path_display : PathDisplayType
+    ///     The type of path display to use. Can be BelowMask or AboveMask.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///     The bytes of the image with the path drawn.
+    ///
+    /// Raises
+    /// ------
+    /// InvalidStyleError
+    ///     If the line width is smaller than 1 (2 for paths with an outline) or larger than the grid size.
+    #[pyo3(name = "draw_path", signature = (
+        travel,
+        percentage,
+        line_width,
+        path_type = PathStyle::DottedWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
+        display_style = PathDisplayType::BelowMask,
+        progress_display_type = PathProgressDisplayType::Travelled
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_draw_path<'py>(
+        &mut self,
+        py: Python<'py>,
+        travel: Travel,
+        percentage: f32,
+        line_width: i32,
+        path_type: PathStyle,
+        display_style: PathDisplayType,
+        progress_display_type: PathProgressDisplayType,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let image = py.allow_threads(|| {
+            self.draw_path(
+                travel,
+                percentage,
+                line_width,
+                path_type,
+                display_style,
+                progress_display_type,
+            )
+        })?;
+        Ok(PyBytes::new(py, &image))
+    }
+
+    /// Returns the full image. If specified, draws the grid, obstacles, and dots.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///    The bytes of the image with the grid, obstacles, and dots drawn.
+    #[pyo3(name = "full_image")]
+    fn py_full_image<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &py.allow_threads(|| self.full_image()))
+    }
+
+    /// Returns the masked image. If specified, draws the grid, obstacles, and dots.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///   The bytes of the image with the grid, obstacles, and dots drawn.
+    #[pyo3(name = "masked_image")]
+    fn py_masked_image<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &py.allow_threads(|| self.masked_image()))
+    }
+
+    /// The main method to get the image bytes.
+    /// Respects the map type and draws the grid, obstacles, and dots if specified.
+    /// The Python GIL is released while drawing, so several maps can be drawn in parallel threads.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///   The bytes of the image with the grid, obstacles, and dots drawn.
+    #[pyo3(name = "get_bits")]
+    fn py_get_bits<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &py.allow_threads(|| self.get_bits()))
+    }
+
+    /// Encodes an image of the map as PNG. Only available if rpg_map was built with the ``image`` feature.
+    ///
+    /// Parameters
+    /// ----------
+    /// bytes : Optional[bytes]
+    ///     The bytes to encode, for example the result of :func:`Map.draw_path` or :func:`Map.draw_background`.
+    ///     Defaults to the result of :func:`Map.get_bits`.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///     The PNG file.
+    #[cfg(feature = "image")]
+    #[pyo3(name = "to_png_bytes", signature = (bytes = None))]
+    fn py_to_png_bytes<'py>(
+        &mut self,
+        py: Python<'py>,
+        bytes: Option<ImageBytes>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let png = py.allow_threads(|| self.to_png_bytes(bytes.map(|bytes| bytes.0)))?;
+        Ok(PyBytes::new(py, &png))
+    }
+
+    /// Saves an image of the map as a PNG file. Only available if rpg_map was built with the ``image`` feature.
+    ///
+    /// Parameters
+    /// ----------
+    /// path : str
+    ///     The path to save the file at.
+    /// bytes : Optional[bytes]
+    ///     The bytes to save, for example the result of :func:`Map.draw_path` or :func:`Map.draw_background`.
+    ///     Defaults to the result of :func:`Map.get_bits`.
+    #[cfg(feature = "image")]
+    #[pyo3(name = "save_png", signature = (path, bytes = None))]
+    fn py_save_png(
+        &mut self,
+        py: Python<'_>,
+        path: std::path::PathBuf,
+        bytes: Option<ImageBytes>,
+    ) -> PyResult<()> {
+        py.allow_threads(|| self.save_png(&path, bytes.map(|bytes| bytes.0)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//! The Python bindings, a thin layer over the Rust API which is only built with the `python` feature
+use crate::structs::map::{
+    Map, MapType, PathDisplayType, PathProgressDisplayType, PathStyle, RevealShape,
+};
+use crate::structs::path::PathPoint;
+use crate::structs::travel::{MovementRange, Travel};
+use pyo3::prelude::*;
+
+pub mod buffer;
+mod errors;
+mod map;
+mod path;
+mod travel;
+
+#[pymodule]
+pub fn rpg_map(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Map>()?;
+    m.add_class::<MapType>()?;
+    m.add_class::<PathStyle>()?;
+    m.add_class::<Travel>()?;
+    m.add_class::<MovementRange>()?;
+    m.add_class::<PathDisplayType>()?;
+    m.add_class::<PathPoint>()?;
+    m.add_class::<PathProgressDisplayType>()?;
+    m.add_class::<RevealShape>()?;
+    errors::add_exceptions(m)?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::structs::path::PathPoint;
+use pyo3::prelude::*;
+use workaround::stubgen;
+
+#[stubgen]
+#[pymethods]
+impl PathPoint {
+    #[new]
+    fn py_new(x: u32, y: u32) -> Self {
+        PathPoint::new(x, y)
+    }
+
+    #[getter]
+    fn get_x(&self) -> u32 {
+        self.x
+    }
+
+    #[getter]
+    fn get_y(&self) -> u32 {
+        self.y
+    }
+
+    pub fn __reduce__(&self, py: Python<'_>) -> PyResult<(Py<PyAny>, (u32, u32))> {
+        Ok((
+            py.get_type::<PathPoint>().into_any().unbind(),
+            (self.x, self.y),
+        ))
+    }
+
+    pub fn __copy__(&self) -> Self {
+        *self
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        *self
+    }
+}
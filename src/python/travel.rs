@@ -0,0 +1,263 @@
+use crate::python::buffer::ImageBytes;
+use crate::structs::map::Map;
+use crate::structs::path::PathPoint;
+use crate::structs::travel::{MovementRange, Travel, TravelOptions};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyTuple};
+use std::collections::HashMap;
+use workaround::stubgen;
+
+#[stubgen]
+#[pymethods]
+impl Travel {
+    #[new]
+    #[pyo3(signature = (map, current_location, destination, smooth = false, snap_destination = false, hierarchical = false))]
+    fn py_new(
+        py: Python<'_>,
+        map: Map,
+        current_location: (u32, u32),
+        destination: (u32, u32),
+        smooth: bool,
+        snap_destination: bool,
+        hierarchical: bool,
+    ) -> PyResult<Travel> {
+        let travel = py.allow_threads(|| {
+            let options = TravelOptions {
+                smooth,
+                snap_destination,
+                hierarchical,
+            };
+            Travel::new(map, current_location, destination, options)
+        })?;
+        Ok(travel)
+    }
+
+    #[getter]
+    fn get_computed_path(&self) -> Vec<PathPoint> {
+        self.computed_path().to_vec()
+    }
+
+    #[getter]
+    fn get_legs(&self) -> Vec<(usize, usize)> {
+        self.legs().to_vec()
+    }
+
+    #[getter]
+    fn get_snap_distance(&self) -> Option<f32> {
+        self.snap_distance()
+    }
+
+    /// Creates a travel which visits all given waypoints in order.
+    /// The paths between two consecutive waypoints (legs) are stitched
+    /// together into one :attr:`Travel.computed_path`.
+    ///
+    /// Parameters
+    /// ----------
+    /// map : Map
+    ///    The map to travel on.
+    /// waypoints : list[tuple[int, int]]
+    ///    The points to visit in order, starting with the current location of the traveler.
+    ///    Requires at least two points.
+    /// smooth : bool
+    ///    If true, the path of every leg is straightened. See :class:`Travel`.
+    /// snap_destination : bool
+    ///    If true, the last waypoint is moved to the closest reachable point if it cannot be reached.
+    ///    See :class:`Travel`.
+    /// hierarchical : bool
+    ///    If true, every leg is found with hierarchical pathfinding. See :class:`Travel`.
+    ///
+    /// Returns
+    /// -------
+    /// Travel
+    ///    The travel along all waypoints.
+    #[staticmethod]
+    #[pyo3(name = "from_waypoints", signature = (map, waypoints, smooth = false, snap_destination = false, hierarchical = false))]
+    fn py_from_waypoints(
+        py: Python<'_>,
+        map: Map,
+        waypoints: Vec<(u32, u32)>,
+        smooth: bool,
+        snap_destination: bool,
+        hierarchical: bool,
+    ) -> PyResult<Travel> {
+        let travel = py.allow_threads(|| {
+            let options = TravelOptions {
+                smooth,
+                snap_destination,
+                hierarchical,
+            };
+            Travel::from_waypoints(map, waypoints, options)
+        })?;
+        Ok(travel)
+    }
+
+    /// Displays the map in a black and white view where white are the
+    /// obstacles and black are the free spaces. This is to debug if
+    /// a fault is with the pathfinding algorithm or the map reduction
+    /// algorithm.
+    ///
+    /// Parameters
+    /// ---------
+    /// map : Map
+    ///   The map to display the black and white view of.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///   The bytes of the black and white view of the map.
+    #[staticmethod]
+    #[pyo3(name = "dbg_map")]
+    fn py_dbg_map(py: Python<'_>, map: Map) -> Bound<'_, PyBytes> {
+        PyBytes::new(py, &py.allow_threads(|| Travel::dbg_map(map)))
+    }
+
+    /// Serializes the travel, including its map and path, to a compact binary format.
+    /// It can be restored with :func:`Travel.from_binary`. This is also used to pickle it.
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///    The state of the travel.
+    #[pyo3(name = "to_binary")]
+    fn py_to_binary<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.to_binary()?))
+    }
+
+    /// Restores a travel serialized with :func:`Travel.to_binary` without computing the path again.
+    ///
+    /// Parameters
+    /// ---------
+    /// data : bytes
+    ///   The bytes created by :func:`Travel.to_binary`.
+    ///
+    /// Returns
+    /// -------
+    /// Travel
+    ///   The restored travel.
+    #[staticmethod]
+    #[pyo3(name = "from_binary")]
+    fn py_from_binary(data: ImageBytes) -> PyResult<Travel> {
+        Ok(Travel::from_binary(&data.0)?)
+    }
+
+    /// Pickles the travel, see :func:`Travel.to_binary`
+    pub fn __reduce__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let from_binary = py.get_type::<Travel>().getattr("from_binary")?;
+        Ok((from_binary, PyTuple::new(py, [self.py_to_binary(py)?])?))
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Checks if there is a path between two points without computing it.
+    /// This is much faster than creating a :class:`Travel`, especially if there is no path.
+    ///
+    /// Parameters
+    /// ---------
+    /// map : Map
+    ///   The map to check on.
+    /// current_location : tuple[int, int]
+    ///   The point to start from.
+    /// destination : tuple[int, int]
+    ///   The point to reach.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    ///   True if the destination can be reached, False if there is no path or one of the points is an obstacle.
+    #[staticmethod]
+    #[pyo3(name = "is_reachable")]
+    fn py_is_reachable(
+        py: Python<'_>,
+        map: PyRef<'_, Map>,
+        current_location: (u32, u32),
+        destination: (u32, u32),
+    ) -> PyResult<bool> {
+        let map: &Map = &map;
+        Ok(py.allow_threads(|| Travel::is_reachable(map, current_location, destination))?)
+    }
+
+    /// Finds the region a point is in. Regions are areas of the map which are
+    /// separated from each other by obstacles, so there is a path between two
+    /// points if and only if they are in the same region.
+    ///
+    /// Parameters
+    /// ---------
+    /// map : Map
+    ///   The map to check on.
+    /// point : tuple[int, int]
+    ///   The point to find the region of.
+    ///
+    /// Returns
+    /// -------
+    /// Optional[int]
+    ///   The id of the region, or None if the point is an obstacle.
+    #[staticmethod]
+    #[pyo3(name = "region_of")]
+    fn py_region_of(
+        py: Python<'_>,
+        map: PyRef<'_, Map>,
+        point: (u32, u32),
+    ) -> PyResult<Option<u32>> {
+        let map: &Map = &map;
+        Ok(py.allow_threads(|| Travel::region_of(map, point))?)
+    }
+
+    /// Finds every point which can be reached from a start point within a movement budget,
+    /// for example to highlight how far a unit can move in one turn.
+    /// Uses the same rules as the pathfinding: every step, including diagonal ones, costs 1,
+    /// or the cost of the terrain if the map has a cost layer (see :func:`Map.with_cost_map`).
+    /// The result can be drawn with :func:`Map.with_movement_range`.
+    ///
+    /// Parameters
+    /// ---------
+    /// map : Map
+    ///   The map to move on.
+    /// start : tuple[int, int]
+    ///   The point to start from.
+    /// budget : int
+    ///   The maximum total movement cost.
+    ///
+    /// Returns
+    /// -------
+    /// MovementRange
+    ///   All reachable points with the cost to reach them.
+    #[staticmethod]
+    #[pyo3(name = "movement_range")]
+    fn py_movement_range(
+        py: Python<'_>,
+        map: PyRef<'_, Map>,
+        start: (u32, u32),
+        budget: u32,
+    ) -> PyResult<MovementRange> {
+        let map: &Map = &map;
+        Ok(py.allow_threads(|| Travel::movement_range(map, start, budget))?)
+    }
+}
+
+#[stubgen]
+#[pymethods]
+impl MovementRange {
+    #[getter]
+    fn get_origin(&self) -> (u32, u32) {
+        self.origin
+    }
+
+    #[getter]
+    fn get_budget(&self) -> u32 {
+        self.budget
+    }
+
+    #[getter]
+    fn get_distances(&self) -> HashMap<(u32, u32), u32> {
+        self.distances.clone()
+    }
+}
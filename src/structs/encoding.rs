@@ -0,0 +1,45 @@
+use crate::structs::error::{Result, RpgMapError};
+use image::{ImageError, ImageFormat, ImageReader, RgbaImage};
+use std::io::Cursor;
+use std::path::Path;
+
+/// Decodes a PNG or WebP image into its RGBA bytes, width and height
+pub fn decode(data: &[u8]) -> Result<(Vec<u8>, u32, u32)> {
+    let image = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .decode()
+        .map_err(image_error)?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    Ok((image.into_raw(), width, height))
+}
+
+/// Reads and decodes a PNG or WebP file
+pub fn decode_file(path: &Path) -> Result<(Vec<u8>, u32, u32)> {
+    decode(&std::fs::read(path)?)
+}
+
+/// Encodes RGBA bytes as a PNG image
+pub fn encode_png(bytes: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>> {
+    let expected = width as usize * height as usize * 4;
+    if bytes.len() != expected {
+        return Err(RpgMapError::SizeMismatch {
+            name: "Image",
+            expected,
+            actual: bytes.len(),
+        });
+    }
+    let image = RgbaImage::from_raw(width, height, bytes).expect("The size was checked above");
+    let mut png = Cursor::new(vec![]);
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(image_error)?;
+    Ok(png.into_inner())
+}
+
+fn image_error(error: ImageError) -> RpgMapError {
+    match error {
+        ImageError::IoError(error) => error.into(),
+        error => RpgMapError::Image(error.to_string()),
+    }
+}
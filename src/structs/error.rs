@@ -0,0 +1,111 @@
+use std::fmt;
+
+/// The error type of all fallible operations of rpg_map
+#[derive(Debug)]
+pub enum RpgMapError {
+    /// An argument has a value which cannot be used
+    InvalidArgument(String),
+    /// A point is outside of the map
+    OutOfBounds {
+        point: (u32, u32),
+        width: u32,
+        height: u32,
+    },
+    /// The start or end point of a path or movement is an obstacle
+    BlockedEndpoint { point: (u32, u32) },
+    /// There is no path between two points
+    NoPath {
+        start: (u32, u32),
+        destination: (u32, u32),
+    },
+    /// The line width cannot be used to draw a path, it has to be between `min` and `max`
+    InvalidStyle { line_width: i32, min: i32, max: i32 },
+    /// An image passed in does not have the size of the map. The sizes are in bytes.
+    SizeMismatch {
+        name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A state could not be serialized
+    Serialize(String),
+    /// A serialized state is invalid
+    Deserialize(String),
+    /// A state was serialized by a newer version of rpg_map
+    UnsupportedVersion(u32),
+    /// An image could not be decoded or encoded
+    Image(String),
+    /// Reading or writing a file failed
+    Io(std::io::Error),
+}
+
+impl RpgMapError {
+    pub(crate) fn invalid(message: impl Into<String>) -> Self {
+        RpgMapError::InvalidArgument(message.into())
+    }
+}
+
+impl fmt::Display for RpgMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpgMapError::InvalidArgument(message) => write!(f, "{message}"),
+            RpgMapError::OutOfBounds {
+                point: (x, y),
+                width,
+                height,
+            } => write!(
+                f,
+                "Point ({x}, {y}) is out of bounds of the {width}x{height} map"
+            ),
+            RpgMapError::BlockedEndpoint { point: (x, y) } => {
+                write!(f, "Point ({x}, {y}) is an obstacle")
+            }
+            RpgMapError::NoPath { start, destination } => write!(
+                f,
+                "No path found from ({}, {}) to ({}, {})",
+                start.0, start.1, destination.0, destination.1
+            ),
+            RpgMapError::InvalidStyle {
+                line_width,
+                min,
+                max,
+            } => write!(
+                f,
+                "Line width {line_width} must be between {min} and {max} for this path style"
+            ),
+            RpgMapError::SizeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{name} must have the same size as the map ({expected} bytes), but has {actual} bytes"
+            ),
+            RpgMapError::Serialize(error) => write!(f, "Failed to serialize: {error}"),
+            RpgMapError::Deserialize(error) => write!(f, "Invalid state: {error}"),
+            RpgMapError::UnsupportedVersion(version) => write!(
+                f,
+                "State version {version} is not supported, the newest supported version is {}",
+                crate::structs::state::STATE_VERSION
+            ),
+            RpgMapError::Image(error) => write!(f, "Invalid image: {error}"),
+            RpgMapError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for RpgMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RpgMapError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RpgMapError {
+    fn from(error: std::io::Error) -> Self {
+        RpgMapError::Io(error)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, RpgMapError>;
@@ -0,0 +1,165 @@
+/// The grid points of a map and which of them are unlocked.
+///
+/// The grid points are every multiple of the grid size along both axes, plus the
+/// last row and column of the map. The unlocked state is a bitset with one bit per
+/// grid point, so unlocking and looking up a point does not depend on how many are unlocked.
+#[derive(Clone, Debug)]
+pub struct ExplorationGrid {
+    width: u32,
+    height: u32,
+    grid_size: u32,
+    columns: usize,
+    rows: usize,
+    bits: Vec<u64>,
+}
+
+impl ExplorationGrid {
+    pub fn new(width: u32, height: u32, grid_size: u32) -> Self {
+        let columns = line_count(width, grid_size);
+        let rows = line_count(height, grid_size);
+        ExplorationGrid {
+            width,
+            height,
+            grid_size,
+            columns,
+            rows,
+            bits: vec![0; (columns * rows).div_ceil(64)],
+        }
+    }
+
+    /// Finds the closest grid point by manhattan distance. Ties go to the smaller coordinate.
+    pub fn closest(&self, point: (u32, u32)) -> (u32, u32) {
+        let x = closest_line(point.0, self.width, self.grid_size);
+        let y = closest_line(point.1, self.height, self.grid_size);
+        if self.index((x, y)).is_some() {
+            return (x, y);
+        }
+        // The bottom right corner is not a grid point if neither the width nor the height
+        // is a multiple of the grid size. The closest point is then next to it in the
+        // last row or in the last column, preferring the row.
+        let last_x = (self.width - 1) / self.grid_size * self.grid_size;
+        let last_y = (self.height - 1) / self.grid_size * self.grid_size;
+        let in_row = (last_x, self.height - 1);
+        let in_column = (self.width - 1, last_y);
+        if distance(point, in_row) <= distance(point, in_column) {
+            in_row
+        } else {
+            in_column
+        }
+    }
+
+    /// Whether a point is an unlocked grid point
+    pub fn contains(&self, point: (u32, u32)) -> bool {
+        self.index(point)
+            .is_some_and(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    /// Unlocks a grid point. Returns false if it already was unlocked or is not a grid point.
+    pub fn insert(&mut self, point: (u32, u32)) -> bool {
+        let Some(index) = self.index(point) else {
+            return false;
+        };
+        let unlocked = self.contains(point);
+        self.bits[index / 64] |= 1 << (index % 64);
+        !unlocked
+    }
+
+    /// Locks a grid point again. Returns false if it was not unlocked.
+    pub fn remove(&mut self, point: (u32, u32)) -> bool {
+        let Some(index) = self.index(point) else {
+            return false;
+        };
+        let unlocked = self.contains(point);
+        self.bits[index / 64] &= !(1 << (index % 64));
+        unlocked
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// The cells of the grid with all four corners unlocked, as their top left and bottom right corners
+    pub fn unlocked_cells(&self) -> impl Iterator<Item = ((u32, u32), (u32, u32))> + '_ {
+        let columns = self.columns.saturating_sub(1);
+        let rows = self.rows.saturating_sub(1);
+        (0..rows)
+            .flat_map(move |row| (0..columns).map(move |column| (column, row)))
+            .filter_map(move |(column, row)| {
+                let top_left = (
+                    line(column, self.width, self.grid_size),
+                    line(row, self.height, self.grid_size),
+                );
+                let bottom_right = (
+                    line(column + 1, self.width, self.grid_size),
+                    line(row + 1, self.height, self.grid_size),
+                );
+                let corners = [
+                    top_left,
+                    (bottom_right.0, top_left.1),
+                    (top_left.0, bottom_right.1),
+                    bottom_right,
+                ];
+                corners
+                    .iter()
+                    .all(|corner| self.contains(*corner))
+                    .then_some((top_left, bottom_right))
+            })
+    }
+
+    /// The index of the bit of a grid point, or None if the point is not a grid point
+    fn index(&self, (x, y): (u32, u32)) -> Option<usize> {
+        let column = line_index(x, self.width, self.grid_size)?;
+        let row = line_index(y, self.height, self.grid_size)?;
+        let last_column =
+            !(self.width - 1).is_multiple_of(self.grid_size) && column == self.columns - 1;
+        let last_row = !(self.height - 1).is_multiple_of(self.grid_size) && row == self.rows - 1;
+        if last_column && last_row {
+            return None; // Only the last row and column are added, not the corner they share
+        }
+        Some(row * self.columns + column)
+    }
+}
+
+/// The number of grid lines along an axis of the given length
+fn line_count(length: u32, grid_size: u32) -> usize {
+    let aligned = ((length - 1) / grid_size + 1) as usize;
+    if (length - 1).is_multiple_of(grid_size) {
+        aligned
+    } else {
+        aligned + 1 // The last pixel
+    }
+}
+
+/// The coordinate of the grid line with the index
+fn line(index: usize, length: u32, grid_size: u32) -> u32 {
+    (index as u32 * grid_size).min(length - 1)
+}
+
+/// The index of the grid line at a coordinate, or None if there is no line
+fn line_index(coordinate: u32, length: u32, grid_size: u32) -> Option<usize> {
+    if coordinate >= length {
+        None
+    } else if coordinate.is_multiple_of(grid_size) {
+        Some((coordinate / grid_size) as usize)
+    } else if coordinate == length - 1 {
+        Some(((length - 1) / grid_size + 1) as usize)
+    } else {
+        None
+    }
+}
+
+/// The closest grid line to a coordinate. Ties go to the smaller line.
+fn closest_line(coordinate: u32, length: u32, grid_size: u32) -> u32 {
+    let coordinate = coordinate.min(length - 1);
+    let below = coordinate / grid_size * grid_size;
+    let above = (below + grid_size).min(length - 1);
+    if above - coordinate < coordinate - below {
+        above
+    } else {
+        below
+    }
+}
+
+fn distance(a: (u32, u32), b: (u32, u32)) -> u32 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
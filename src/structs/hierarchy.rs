@@ -0,0 +1,449 @@
+use crate::structs::path::{step_cost, PathPoint};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// The width and height of a cluster in cells
+pub const CLUSTER_SIZE: u32 = 32;
+
+/// Borders where more neighbouring cells than this can be crossed get a portal at
+/// both ends instead of a single one in the middle, which keeps paths closer to optimal
+const MAX_SINGLE_PORTAL_WIDTH: usize = 6;
+
+/// An inclusive rectangle of cells: (min x, min y, max x, max y)
+type Bounds = (u32, u32, u32, u32);
+
+/// Two neighbouring cells in different clusters: (cell inside, cell outside)
+type Crossing = ((u32, u32), (u32, u32));
+
+/// An abstraction of a navigation grid for hierarchical pathfinding (HPA*).
+///
+/// The grid is split into square clusters. Wherever two neighbouring clusters
+/// can be crossed, a pair of portal cells is added to a graph, and all portals
+/// of a cluster are connected with the cost of the cheapest path between them
+/// inside of it. A long route is then found on this small graph and only
+/// refined into cells one cluster at a time.
+pub struct Hierarchy {
+    cluster_size: u32,
+    width: u32,
+    height: u32,
+    clusters_x: u32,
+    /// The cell of every portal
+    portals: Vec<(u32, u32)>,
+    /// The outgoing edges (target portal, cost) of every portal
+    edges: Vec<Vec<(usize, u32)>>,
+    /// The portals inside of every cluster
+    cluster_portals: Vec<Vec<usize>>,
+    portal_ids: HashMap<(u32, u32), usize>,
+}
+
+impl Hierarchy {
+    pub fn new(grid: &[Vec<u8>], costs: Option<&[Vec<u8>]>, cluster_size: u32) -> Self {
+        let height = grid.len() as u32;
+        let width = grid.first().map_or(0, |row| row.len()) as u32;
+        let clusters_x = width.div_ceil(cluster_size);
+        let clusters_y = height.div_ceil(cluster_size);
+        let mut hierarchy = Hierarchy {
+            cluster_size,
+            width,
+            height,
+            clusters_x,
+            portals: vec![],
+            edges: vec![],
+            cluster_portals: vec![vec![]; (clusters_x * clusters_y) as usize],
+            portal_ids: HashMap::new(),
+        };
+
+        for cluster_y in 0..clusters_y {
+            for cluster_x in 0..clusters_x {
+                let (min_x, min_y, max_x, max_y) = hierarchy.bounds_of(cluster_x, cluster_y);
+                if cluster_x + 1 < clusters_x {
+                    let border = (min_y..=max_y)
+                        .map(|y| ((max_x, y), (max_x + 1, y)))
+                        .collect::<Vec<_>>();
+                    hierarchy.add_entrances(grid, costs, &border);
+                }
+                if cluster_y + 1 < clusters_y {
+                    let border = (min_x..=max_x)
+                        .map(|x| ((x, max_y), (x, max_y + 1)))
+                        .collect::<Vec<_>>();
+                    hierarchy.add_entrances(grid, costs, &border);
+                }
+                if cluster_x + 1 < clusters_x && cluster_y + 1 < clusters_y {
+                    hierarchy.add_corner_crossings(grid, costs, (max_x, max_y));
+                }
+            }
+        }
+
+        for cluster in 0..hierarchy.cluster_portals.len() {
+            let bounds =
+                hierarchy.bounds_of(cluster as u32 % clusters_x, cluster as u32 / clusters_x);
+            let members = hierarchy.cluster_portals[cluster].clone();
+            // Without obstacles or costs the cheapest path is simply the chessboard distance
+            let (min_x, min_y, max_x, max_y) = bounds;
+            let open = costs.is_none()
+                && grid[min_y as usize..=max_y as usize]
+                    .iter()
+                    .all(|row| !row[min_x as usize..=max_x as usize].contains(&1));
+            for (i, &from) in members.iter().enumerate() {
+                let from_cell = hierarchy.portals[from];
+                let distances = if open {
+                    vec![]
+                } else {
+                    distances_within(grid, costs, from_cell, bounds)
+                };
+                for &to in &members[i + 1..] {
+                    let to_cell = hierarchy.portals[to];
+                    let cost = if open {
+                        from_cell
+                            .0
+                            .abs_diff(to_cell.0)
+                            .max(from_cell.1.abs_diff(to_cell.1))
+                    } else {
+                        distances[index_within(to_cell, bounds)]
+                    };
+                    if cost == u32::MAX {
+                        continue;
+                    }
+                    // Walking the same path back enters `from` instead of `to`
+                    let back = cost + step_cost(costs, PathPoint::from_tuple(from_cell))
+                        - step_cost(costs, PathPoint::from_tuple(to_cell));
+                    hierarchy.edges[from].push((to, cost));
+                    hierarchy.edges[to].push((from, back));
+                }
+            }
+        }
+
+        hierarchy
+    }
+
+    /// Finds a path between two free cells. The path is not guaranteed to be
+    /// the shortest one, but it is usually close to it and much faster to find.
+    pub fn find_path(
+        &self,
+        grid: &[Vec<u8>],
+        costs: Option<&[Vec<u8>]>,
+        start: (u32, u32),
+        end: (u32, u32),
+    ) -> Option<Vec<PathPoint>> {
+        let start_cluster = self.cluster_of(start);
+        let end_cluster = self.cluster_of(end);
+        if start_cluster == end_cluster {
+            let bounds = self.cluster_bounds(start_cluster);
+            if let Some(path) = path_within(grid, costs, start, end, bounds) {
+                return Some(path);
+            }
+        }
+
+        // The start and end are added to the graph as two temporary nodes
+        let start_node = self.portals.len();
+        let end_node = start_node + 1;
+        let start_distances =
+            distances_within(grid, costs, start, self.cluster_bounds(start_cluster));
+        let end_distances = distances_within(grid, costs, end, self.cluster_bounds(end_cluster));
+        let start_edges = self.cluster_portals[start_cluster]
+            .iter()
+            .map(|&portal| (portal, self.within(&start_distances, start_cluster, portal)))
+            .filter(|&(_, cost)| cost != u32::MAX)
+            .collect::<Vec<_>>();
+        // The distances are measured from the end, so the cost of entering the
+        // portal is swapped for the cost of entering the end
+        let end_cost = |portal: usize| {
+            let cost = self.within(&end_distances, end_cluster, portal);
+            (cost != u32::MAX).then(|| {
+                cost + step_cost(costs, PathPoint::from_tuple(end))
+                    - step_cost(costs, PathPoint::from_tuple(self.portals[portal]))
+            })
+        };
+        let position = |node: usize| match node {
+            node if node == start_node => start,
+            node if node == end_node => end,
+            node => self.portals[node],
+        };
+        let heuristic = |node: usize| {
+            let (x, y) = position(node);
+            x.abs_diff(end.0).max(y.abs_diff(end.1))
+        };
+
+        let mut g_score = vec![u32::MAX; self.portals.len() + 2];
+        let mut came_from = vec![usize::MAX; self.portals.len() + 2];
+        let mut open_set = BinaryHeap::new();
+        g_score[start_node] = 0;
+        open_set.push(Reverse((heuristic(start_node), start_node)));
+
+        while let Some(Reverse((_, current))) = open_set.pop() {
+            if current == end_node {
+                break;
+            }
+            let mut neighbors = if current == start_node {
+                start_edges.clone()
+            } else {
+                self.edges[current].clone()
+            };
+            if current != start_node && self.cluster_of(self.portals[current]) == end_cluster {
+                neighbors.extend(end_cost(current).map(|cost| (end_node, cost)));
+            }
+            for (next, cost) in neighbors {
+                let tentative_g_score = g_score[current] + cost;
+                if tentative_g_score < g_score[next] {
+                    g_score[next] = tentative_g_score;
+                    came_from[next] = current;
+                    open_set.push(Reverse((tentative_g_score + heuristic(next), next)));
+                }
+            }
+        }
+
+        if g_score[end_node] == u32::MAX {
+            return None;
+        }
+        let mut nodes = vec![end_node];
+        while let Some(&node) = nodes.last().filter(|&&node| node != start_node) {
+            nodes.push(came_from[node]);
+        }
+        nodes.reverse();
+
+        // Refine every step of the abstract path into cells
+        let mut path = vec![PathPoint::from_tuple(start)];
+        for step in nodes.windows(2) {
+            let (from, to) = (position(step[0]), position(step[1]));
+            if from == to {
+                continue;
+            }
+            if from.0.abs_diff(to.0) <= 1 && from.1.abs_diff(to.1) <= 1 {
+                path.push(PathPoint::from_tuple(to));
+                continue;
+            }
+            let bounds = self.cluster_bounds(self.cluster_of(from));
+            let refined = path_within(grid, costs, from, to, bounds)?;
+            path.extend(refined.into_iter().skip(1));
+        }
+        Some(path)
+    }
+
+    fn add_entrances(&mut self, grid: &[Vec<u8>], costs: Option<&[Vec<u8>]>, border: &[Crossing]) {
+        let free = |p: (u32, u32)| grid[p.1 as usize][p.0 as usize] != 1;
+        let crossable = border
+            .iter()
+            .map(|&(inside, outside)| free(inside) && free(outside))
+            .collect::<Vec<_>>();
+
+        // Every run of cells which can be crossed straight is one entrance
+        let mut i = 0;
+        while i < border.len() {
+            if !crossable[i] {
+                i += 1;
+                continue;
+            }
+            let run_start = i;
+            while i < border.len() && crossable[i] {
+                i += 1;
+            }
+            let run_end = i - 1;
+            if run_end - run_start + 1 < MAX_SINGLE_PORTAL_WIDTH {
+                let middle = (run_start + run_end) / 2;
+                self.add_portal_pair(costs, border[middle]);
+            } else {
+                self.add_portal_pair(costs, border[run_start]);
+                self.add_portal_pair(costs, border[run_end]);
+            }
+        }
+
+        // Paths can also cross diagonally where no straight crossing is close by
+        for i in 0..border.len().saturating_sub(1) {
+            if crossable[i] || crossable[i + 1] {
+                continue;
+            }
+            let (inside, outside) = border[i];
+            let (next_inside, next_outside) = border[i + 1];
+            if free(inside) && free(next_outside) {
+                self.add_portal_pair(costs, (inside, next_outside));
+            }
+            if free(next_inside) && free(outside) {
+                self.add_portal_pair(costs, (next_inside, outside));
+            }
+        }
+    }
+
+    /// Where four clusters meet, paths can also step diagonally between the two clusters
+    /// which only share the corner. These steps are added where no straight crossing is next to them.
+    fn add_corner_crossings(
+        &mut self,
+        grid: &[Vec<u8>],
+        costs: Option<&[Vec<u8>]>,
+        (x, y): (u32, u32),
+    ) {
+        let free = |p: (u32, u32)| grid[p.1 as usize][p.0 as usize] != 1;
+        let (top_left, top_right) = ((x, y), (x + 1, y));
+        let (bottom_left, bottom_right) = ((x, y + 1), (x + 1, y + 1));
+        for (crossing, sides) in [
+            ((top_left, bottom_right), [top_right, bottom_left]),
+            ((top_right, bottom_left), [top_left, bottom_right]),
+        ] {
+            if free(crossing.0) && free(crossing.1) && !sides.into_iter().any(free) {
+                self.add_portal_pair(costs, crossing);
+            }
+        }
+    }
+
+    fn add_portal_pair(&mut self, costs: Option<&[Vec<u8>]>, (inside, outside): Crossing) {
+        let from = self.add_portal(inside);
+        let to = self.add_portal(outside);
+        self.edges[from].push((to, step_cost(costs, PathPoint::from_tuple(outside))));
+        self.edges[to].push((from, step_cost(costs, PathPoint::from_tuple(inside))));
+    }
+
+    fn add_portal(&mut self, cell: (u32, u32)) -> usize {
+        if let Some(&id) = self.portal_ids.get(&cell) {
+            return id;
+        }
+        let id = self.portals.len();
+        self.portals.push(cell);
+        self.edges.push(vec![]);
+        self.portal_ids.insert(cell, id);
+        let cluster = self.cluster_of(cell);
+        self.cluster_portals[cluster].push(id);
+        id
+    }
+
+    /// Looks up the distance to a portal in distances computed within a cluster
+    fn within(&self, distances: &[u32], cluster: usize, portal: usize) -> u32 {
+        distances[index_within(self.portals[portal], self.cluster_bounds(cluster))]
+    }
+
+    fn cluster_of(&self, cell: (u32, u32)) -> usize {
+        let cluster_x = cell.0 / self.cluster_size;
+        let cluster_y = cell.1 / self.cluster_size;
+        (cluster_y * self.clusters_x + cluster_x) as usize
+    }
+
+    fn cluster_bounds(&self, cluster: usize) -> Bounds {
+        self.bounds_of(
+            cluster as u32 % self.clusters_x,
+            cluster as u32 / self.clusters_x,
+        )
+    }
+
+    fn bounds_of(&self, cluster_x: u32, cluster_y: u32) -> Bounds {
+        (
+            cluster_x * self.cluster_size,
+            cluster_y * self.cluster_size,
+            ((cluster_x + 1) * self.cluster_size).min(self.width) - 1,
+            ((cluster_y + 1) * self.cluster_size).min(self.height) - 1,
+        )
+    }
+}
+
+fn index_within(cell: (u32, u32), (min_x, min_y, max_x, _): Bounds) -> usize {
+    ((cell.1 - min_y) * (max_x - min_x + 1) + (cell.0 - min_x)) as usize
+}
+
+/// The free neighbours of a cell (including diagonal ones) which are within the bounds
+fn neighbors_within(
+    grid: &[Vec<u8>],
+    (x, y): (u32, u32),
+    (min_x, min_y, max_x, max_y): Bounds,
+) -> impl Iterator<Item = (u32, u32)> + '_ {
+    const MOVES: [(i32, i32); 8] = [
+        (0, 1),
+        (0, -1),
+        (1, 0),
+        (-1, 0),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+    MOVES.into_iter().filter_map(move |(dx, dy)| {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+        let inside = nx >= min_x && nx <= max_x && ny >= min_y && ny <= max_y;
+        (inside && grid[ny as usize][nx as usize] != 1).then_some((nx, ny))
+    })
+}
+
+/// The cost of the cheapest path from `start` to every cell within the bounds
+/// (Dijkstra's algorithm, or a breadth first search if every step costs 1).
+/// Unreachable cells are `u32::MAX`.
+fn distances_within(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    start: (u32, u32),
+    bounds: Bounds,
+) -> Vec<u32> {
+    let (min_x, min_y, max_x, max_y) = bounds;
+    let mut distances = vec![u32::MAX; ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize];
+    distances[index_within(start, bounds)] = 0;
+
+    if costs.is_none() {
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            let distance = distances[index_within(cell, bounds)];
+            for next in neighbors_within(grid, cell, bounds) {
+                if distances[index_within(next, bounds)] == u32::MAX {
+                    distances[index_within(next, bounds)] = distance + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        return distances;
+    }
+
+    let mut open_set = BinaryHeap::new();
+    open_set.push(Reverse((0, start)));
+
+    while let Some(Reverse((distance, cell))) = open_set.pop() {
+        if distance > distances[index_within(cell, bounds)] {
+            continue; // Already reached with a lower cost
+        }
+        for next in neighbors_within(grid, cell, bounds) {
+            let next_distance = distance + step_cost(costs, PathPoint::from_tuple(next));
+            if next_distance < distances[index_within(next, bounds)] {
+                distances[index_within(next, bounds)] = next_distance;
+                open_set.push(Reverse((next_distance, next)));
+            }
+        }
+    }
+
+    distances
+}
+
+/// A* between two cells which never leaves the bounds
+fn path_within(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    start: (u32, u32),
+    end: (u32, u32),
+    bounds: Bounds,
+) -> Option<Vec<PathPoint>> {
+    let (min_x, min_y, max_x, max_y) = bounds;
+    let size = ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize;
+    let mut g_score = vec![u32::MAX; size];
+    let mut came_from = vec![None; size];
+    let mut open_set = BinaryHeap::new();
+    let heuristic = |(x, y): (u32, u32)| x.abs_diff(end.0).max(y.abs_diff(end.1));
+    g_score[index_within(start, bounds)] = 0;
+    open_set.push(Reverse((heuristic(start), start)));
+
+    while let Some(Reverse((_, current))) = open_set.pop() {
+        if current == end {
+            let mut path = vec![PathPoint::from_tuple(end)];
+            let mut cell = end;
+            while let Some(parent) = came_from[index_within(cell, bounds)] {
+                path.push(PathPoint::from_tuple(parent));
+                cell = parent;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        let current_g_score = g_score[index_within(current, bounds)];
+        for next in neighbors_within(grid, current, bounds) {
+            let tentative_g_score = current_g_score + step_cost(costs, PathPoint::from_tuple(next));
+            if tentative_g_score < g_score[index_within(next, bounds)] {
+                g_score[index_within(next, bounds)] = tentative_g_score;
+                came_from[index_within(next, bounds)] = Some(current);
+                open_set.push(Reverse((tentative_g_score + heuristic(next), next)));
+            }
+        }
+    }
+
+    None
+}
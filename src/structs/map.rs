@@ -1,11 +1,26 @@
+#[cfg(feature = "image")]
+use crate::structs::encoding;
+use crate::structs::error::{Result, RpgMapError};
+use crate::structs::exploration::ExplorationGrid;
+use crate::structs::navigation::{NavigationCache, NavigationGrid};
 use crate::structs::path::PathPoint;
-use crate::structs::travel::Travel;
+use crate::structs::state::{hash_bytes, MapState, STATE_VERSION};
+use crate::structs::travel::{MovementRange, Travel};
+use crate::structs::vision;
 use geo::{Contains, Coord, LineString, Point, Polygon};
-use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use workaround::stubgen;
 
-const TRANSPARENT_THRESHOLD: u8 = 160; // anything below 160 appears basically fully transparent
-                                       // It also causes issues with tests
+pub const TRANSPARENT_THRESHOLD: u8 = 160; // anything below 160 appears basically fully transparent
+                                           // It also causes issues with tests
+const DEFAULT_BUFFER: u32 = 5; // clearance in px paths keep from obstacles by default
+const DEFAULT_EXPLORED_FOG: f32 = 0.5; // explored areas are half covered by the fog by default
+
+pub type Overlay = (Vec<(u32, u32)>, [u8; 4]); // points, color
 
 /// The reveal type of the map.
 ///
@@ -17,13 +32,17 @@ const TRANSPARENT_THRESHOLD: u8 = 160; // anything below 160 appears basically f
 ///    The map reveals all the unlocked points.
 /// Full
 ///    The map reveals all the points.
+/// Explored
+///    The map reveals the current position, which is the last unlocked point, and dims all
+///    other unlocked points. See :func:`Map.with_explored_fog`.
 #[stubgen]
-#[pyclass(eq, eq_int)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map", eq, eq_int))]
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Serialize, Deserialize)]
 pub enum MapType {
     Hidden,
     Limited,
     Full,
+    Explored,
 }
 
 /// The style of the path.
@@ -41,7 +60,7 @@ pub enum MapType {
 /// DottedWithOutline
 ///    The path is drawn as a dotted line with an outline.
 #[stubgen]
-#[pyclass(eq)]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map", eq))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PathStyle {
     Debug(),
@@ -62,7 +81,7 @@ pub enum PathStyle {
 /// Progress
 ///   The path is drawn from the start to the destination. The path already travelled is converted to greyscale.
 #[stubgen]
-#[pyclass(eq)]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map", eq))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PathProgressDisplayType {
     Remaining,
@@ -79,19 +98,203 @@ pub enum PathProgressDisplayType {
 /// AboveMask
 ///   The path is always drawn above the mask.
 #[stubgen]
-#[pyclass(eq)]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map", eq))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PathDisplayType {
     BelowMask,
     AboveMask,
 }
 
+/// The shape of the area revealed around a point. All sizes are in pixels.
+///
+/// Attributes
+/// ---------
+/// Circle
+///   A circle with a radius.
+/// Square
+///   A square which extends the given distance from the point to each side.
+/// Ellipse
+///   An ellipse with a horizontal and a vertical radius.
+/// Polygon
+///   A polygon with at least 3 corners, given as offsets from the point.
+#[stubgen]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map", eq))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RevealShape {
+    Circle(u32),
+    Square(u32),
+    Ellipse(u32, u32),
+    Polygon(Vec<(i32, i32)>),
+}
+
+impl RevealShape {
+    /// Checks the shape, which may extend at most `max_extent` pixels from its point
+    fn validate(&self, max_extent: u32) -> Result<()> {
+        if self.extent() > max_extent {
+            return Err(RpgMapError::invalid(format!(
+                "A reveal shape can extend at most {max_extent} pixels (the diagonal of the map), got {}",
+                self.extent()
+            )));
+        }
+        match self {
+            RevealShape::Ellipse(rx, ry) if *rx == 0 || *ry == 0 => Err(RpgMapError::invalid(
+                format!("Both radii of an ellipse must be at least 1, got {rx} and {ry}"),
+            )),
+            RevealShape::Polygon(points) if points.len() < 3 => Err(RpgMapError::invalid(format!(
+                "A polygon needs at least 3 points, got {}",
+                points.len()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// How far the shape extends from its point in any direction
+    fn extent(&self) -> u32 {
+        match self {
+            RevealShape::Circle(radius) | RevealShape::Square(radius) => *radius,
+            RevealShape::Ellipse(rx, ry) => (*rx).max(*ry),
+            RevealShape::Polygon(points) => points
+                .iter()
+                .map(|(x, y)| x.unsigned_abs().max(y.unsigned_abs()))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Returns a function which tells how far a pixel at an offset from the point is
+    /// inside the shape, measured from the edge of the shape. None if it is outside of it.
+    /// The function is called for every pixel around the point, so anything which only
+    /// depends on the shape (like the polygon) is built once up front.
+    fn depth(&self) -> Box<dyn Fn(i32, i32) -> Option<f32> + '_> {
+        match self {
+            RevealShape::Circle(radius) => {
+                let radius = *radius as i64;
+                Box::new(move |dx, dy| {
+                    let (dx, dy) = (dx as i64, dy as i64);
+                    (dx * dx + dy * dy <= radius * radius)
+                        .then(|| radius as f32 - ((dx * dx + dy * dy) as f32).sqrt())
+                })
+            }
+            RevealShape::Square(half) => Box::new(move |dx, dy| {
+                let distance = dx.unsigned_abs().max(dy.unsigned_abs());
+                (distance <= *half).then(|| (*half - distance) as f32)
+            }),
+            RevealShape::Ellipse(rx, ry) => {
+                let (rx, ry) = (*rx as f32, *ry as f32);
+                Box::new(move |dx, dy| {
+                    let scaled = ((dx as f32 / rx).powi(2) + (dy as f32 / ry).powi(2)).sqrt();
+                    (scaled <= 1.0).then(|| (1.0 - scaled) * rx.min(ry))
+                })
+            }
+            RevealShape::Polygon(points) => {
+                let polygon = to_polygon(points.iter().map(|&(x, y)| (x as f64, y as f64)));
+                Box::new(move |dx, dy| {
+                    let point = Point::new(dx as f64, dy as f64);
+                    if !polygon.contains(&point) && !polygon.exterior().contains(&point) {
+                        return None;
+                    }
+                    Some(distance_to_edge(&polygon, point) as f32)
+                })
+            }
+        }
+    }
+}
+
+/// Hashes a value with the hasher of the standard library
+fn hash_value(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Creates a polygon from its corners
+fn to_polygon(points: impl Iterator<Item = (f64, f64)>) -> Polygon {
+    let exterior = points.map(|(x, y)| Coord { x, y }).collect::<Vec<Coord>>();
+    Polygon::new(LineString::from(exterior), vec![])
+}
+
+/// The distance from a point to the closest edge of a polygon
+fn distance_to_edge(polygon: &Polygon, point: Point) -> f64 {
+    polygon
+        .exterior()
+        .lines()
+        .map(|line| {
+            let (start, end) = (line.start, line.end);
+            let (ex, ey) = (end.x - start.x, end.y - start.y);
+            let length_sq = ex * ex + ey * ey;
+            let t = if length_sq == 0.0 {
+                0.0
+            } else {
+                (((point.x() - start.x) * ex + (point.y() - start.y) * ey) / length_sq)
+                    .clamp(0.0, 1.0)
+            };
+            let (px, py) = (start.x + t * ex, start.y + t * ey);
+            ((point.x() - px).powi(2) + (point.y() - py).powi(2)).sqrt()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// What hidden areas of the map are covered with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Fog {
+    /// A solid color, partially transparent if its alpha is below 255
+    Color([u8; 4]),
+    /// An RGBA image which is repeated over the map
+    Texture {
+        bytes: Vec<u8>,
+        width: u32,
+        height: u32,
+    },
+    /// The map itself, darkened and desaturated. Both factors are between 0 and 1.
+    Shade { brightness: f32, saturation: f32 },
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Fog::Color([0, 0, 0, 255])
+    }
+}
+
+impl Fog {
+    /// The color of the fog at a pixel of the map which has the color `pixel`
+    fn color_at(&self, x: u32, y: u32, pixel: &[u8]) -> [u8; 4] {
+        match self {
+            Fog::Color(color) => *color,
+            Fog::Texture {
+                bytes,
+                width,
+                height,
+            } => {
+                let index = ((y % height) * width + x % width) as usize * 4;
+                [
+                    bytes[index],
+                    bytes[index + 1],
+                    bytes[index + 2],
+                    bytes[index + 3],
+                ]
+            }
+            Fog::Shade {
+                brightness,
+                saturation,
+            } => {
+                let gray =
+                    0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32;
+                let shade = |channel: u8| {
+                    ((gray + (channel as f32 - gray) * saturation) * brightness).round() as u8
+                };
+                [shade(pixel[0]), shade(pixel[1]), shade(pixel[2]), 255]
+            }
+        }
+    }
+}
+
 /// A class representing a map.
 ///
 /// Parameters
 /// ----------
-/// bytes : List[int]
-///     The bytes of the image.
+/// bytes : bytes
+///     The RGBA bytes of the image. Any object supporting the buffer protocol,
+///     like a ``bytearray``, ``memoryview`` or NumPy array, can be passed as well.
 /// width : int
 ///     The width of the image.
 /// height : int
@@ -104,7 +307,8 @@ pub enum PathDisplayType {
 /// unlocked : List[Tuple[int, int]]
 ///     The points that are unlocked on the map.
 /// special_points : List[Tuple[int, int]]
-///     The special points on the map. Used to draw the path.
+///     The special points on the map. Used to draw the path. They reveal a larger area,
+///     which can be changed with :func:`Map.with_reveal_shape`.
 /// obstacles : List[List[List[Tuple[int, int]]]]
 ///     The obstacles on the map. Used to draw the path.
 /// background : Optional[List[int]]
@@ -117,67 +321,58 @@ pub enum PathDisplayType {
 ///     The height of the map.
 /// unlocked : List[Tuple[int, int]]
 ///     The points that are unlocked on the map.
+///
+/// Raises
+/// ------
+/// SizeMismatchError
+///     If there are not exactly ``width * height * 4`` bytes.
+/// OutOfBoundsError
+///     If an unlocked point, special point or obstacle point is outside of the map.
+/// ValueError
+///     If the width, height or grid size is 0.
 #[stubgen]
-#[pyclass]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map"))]
 #[derive(Clone)]
 pub struct Map {
-    #[pyo3(get)]
-    pub width: u32,
-    #[pyo3(get)]
-    pub height: u32,
+    width: u32,
+    height: u32,
     bytes: Vec<u8>,
+    bytes_hash: String, // see `hash_bytes`, the bytes never change after creating the map
+    terrain_hash: u64,  // hash of the size, bytes and obstacles for the navigation fingerprint
     grid_size: u32,
-    #[pyo3(get)]
-    unlocked: Vec<(u32, u32)>,
-    grid_points: Vec<(u32, u32)>,
+    unlocked: Vec<(u32, u32)>,    // in the order they were unlocked
+    exploration: ExplorationGrid, // the unlocked grid points as a bitset
     special_points: Vec<(u32, u32)>,
-    pub obstacles: Vec<Vec<(u32, u32)>>,
+    obstacles: Vec<Vec<(u32, u32)>>,
     pub map_type: MapType,
     draw_obstacles: bool,
     dots: Vec<(u32, u32, [u8; 4], u32)>, // x, y, color, radius
+    overlays: Vec<Overlay>,
     should_draw_with_grid: bool,
     should_draw_extras: bool,
+    cost_map: Option<Vec<u8>>, // movement cost of every pixel, used for pathfinding
+    cost_map_hash: u64,        // hashed once when the cost layer is set
+    edge_buffer: u32,          // clearance of paths from transparent pixels
+    obstacle_buffer: u32,      // clearance of paths from obstacles
+    obstacle_buffers: BTreeMap<usize, u32>, // clearance of single obstacles by index
+    fog_feather: u32,          // width in px of the fade from revealed areas into the fog
+    fog: Fog,
+    explored_fog: f32, // how much fog covers explored areas which are not currently visible
+    current: Option<(u32, u32)>, // the current position on an explored map
+    line_of_sight: bool, // whether obstacles and walls block the view of unlocked points
+    reveal_shapes: BTreeMap<(u32, u32), RevealShape>, // areas revealed around single points
+    regions: BTreeMap<String, Vec<(u32, u32)>>, // named polygons which are revealed at once
+    unlocked_regions: BTreeSet<String>,
+    decay: Option<u32>, // ticks after which unlocked points are hidden again
+    clock: u64,         // ticks passed, see `tick`
+    last_seen: BTreeMap<(u32, u32), u64>, // tick each unlocked point was last visited at
+    navigation: NavigationCache,
 }
 
-/// Calculates the grid points of the map
-fn calculate_grid_points(width: u32, height: u32, grid_size: u32) -> Vec<(u32, u32)> {
-    let mut grid_points = Vec::new();
-
-    // calculate intersection points
-    for y in (0..height).step_by(grid_size as usize) {
-        for x in (0..width).step_by(grid_size as usize) {
-            grid_points.push((x, y));
-        }
-    }
-
-    // Calculate last intersection points row
-    for x in (0..width).step_by(grid_size as usize) {
-        grid_points.push((x, height - 1));
-    }
-
-    // Calculate last intersection points column
-    for y in (0..height).step_by(grid_size as usize) {
-        grid_points.push((width - 1, y));
-    }
-
-    grid_points
-}
-
-#[stubgen]
-#[pymethods]
 impl Map {
-    #[new]
+    /// Creates a map, see :class:`Map`. Returns an error if the arguments do not fit together,
+    /// for example if there are not exactly `width * height * 4` bytes.
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (
-        bytes,
-        width,
-        height,
-        grid_size,
-        map_type = MapType::Full,
-        unlocked = vec![],
-        special_points = vec![],
-        obstacles = vec![]
-    ))]
     pub fn new(
         bytes: Vec<u8>,
         width: u32,
@@ -187,154 +382,482 @@ impl Map {
         unlocked: Vec<(u32, u32)>,
         special_points: Vec<(u32, u32)>,
         obstacles: Vec<Vec<(u32, u32)>>,
-    ) -> Self {
-        let grid_points = calculate_grid_points(width, height, grid_size);
-        Map {
+    ) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(RpgMapError::invalid(format!(
+                "The map must be at least 1x1 pixels, got {width}x{height}"
+            )));
+        }
+        let expected = width as usize * height as usize * 4;
+        if bytes.len() != expected {
+            return Err(RpgMapError::SizeMismatch {
+                name: "Image",
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        if grid_size == 0 {
+            return Err(RpgMapError::invalid("Grid size must be at least 1"));
+        }
+        let points = unlocked
+            .iter()
+            .chain(&special_points)
+            .chain(obstacles.iter().flatten());
+        if let Some(point) = points
+            .into_iter()
+            .find(|(x, y)| *x >= width || *y >= height)
+        {
+            return Err(RpgMapError::OutOfBounds {
+                point: *point,
+                width,
+                height,
+            });
+        }
+
+        let mut exploration = ExplorationGrid::new(width, height, grid_size);
+        for point in &unlocked {
+            exploration.insert(*point);
+        }
+        let current = unlocked.last().copied();
+        Ok(Map {
             width,
             height,
+            terrain_hash: hash_value((width, height, hash_bytes(&bytes), &obstacles)),
+            bytes_hash: hash_bytes(&bytes),
             bytes,
             grid_size,
             unlocked,
-            grid_points,
+            exploration,
             special_points,
             obstacles,
             map_type,
             draw_obstacles: false,
             dots: Vec::new(),
+            overlays: Vec::new(),
             should_draw_with_grid: false,
             should_draw_extras: true,
+            cost_map: None,
+            cost_map_hash: hash_value(None::<Vec<u8>>),
+            edge_buffer: DEFAULT_BUFFER,
+            obstacle_buffer: DEFAULT_BUFFER,
+            obstacle_buffers: BTreeMap::new(),
+            fog_feather: 0,
+            fog: Fog::default(),
+            explored_fog: DEFAULT_EXPLORED_FOG,
+            current,
+            line_of_sight: false,
+            reveal_shapes: BTreeMap::new(),
+            regions: BTreeMap::new(),
+            unlocked_regions: BTreeSet::new(),
+            decay: None,
+            clock: 0,
+            last_seen: BTreeMap::new(),
+            navigation: NavigationCache::default(),
+        })
+    }
+
+    /// Creates a map from a PNG or WebP file, see :func:`Map.from_file`
+    #[cfg(feature = "image")]
+    pub fn from_file(
+        path: &std::path::Path,
+        grid_size: u32,
+        map_type: MapType,
+        unlocked: Vec<(u32, u32)>,
+        special_points: Vec<(u32, u32)>,
+        obstacles: Vec<Vec<(u32, u32)>>,
+    ) -> Result<Map> {
+        let (bytes, width, height) = encoding::decode_file(path)?;
+        Map::new(
+            bytes,
+            width,
+            height,
+            grid_size,
+            map_type,
+            unlocked,
+            special_points,
+            obstacles,
+        )
+    }
+
+    /// Creates a map from the contents of a PNG or WebP file, see :func:`Map.from_png_bytes`
+    #[cfg(feature = "image")]
+    pub fn from_png_bytes(
+        data: &[u8],
+        grid_size: u32,
+        map_type: MapType,
+        unlocked: Vec<(u32, u32)>,
+        special_points: Vec<(u32, u32)>,
+        obstacles: Vec<Vec<(u32, u32)>>,
+    ) -> Result<Map> {
+        let (bytes, width, height) = encoding::decode(data)?;
+        Map::new(
+            bytes,
+            width,
+            height,
+            grid_size,
+            map_type,
+            unlocked,
+            special_points,
+            obstacles,
+        )
+    }
+
+    /// Serializes the full state of the map to JSON, see :func:`Map.to_json`
+    pub fn to_json(&self, include_bytes: bool) -> Result<String> {
+        self.state(include_bytes).to_json()
+    }
+
+    /// Restores a map serialized with `to_json`, see :func:`Map.from_json`
+    pub fn from_json(data: &str, bytes: Option<Vec<u8>>) -> Result<Map> {
+        Map::from_state(MapState::from_json(data)?, bytes)
+    }
+
+    /// Serializes the full state of the map to a binary format, see :func:`Map.to_binary`
+    pub fn to_binary(&self, include_bytes: bool) -> Result<Vec<u8>> {
+        self.state(include_bytes).to_binary()
+    }
+
+    /// Restores a map serialized with `to_binary`, see :func:`Map.from_binary`
+    pub fn from_binary(data: &[u8], bytes: Option<Vec<u8>>) -> Result<Map> {
+        Map::from_state(MapState::from_binary(data)?, bytes)
+    }
+
+    /// Adds a dot to be drawn on the map, see :func:`Map.with_dot`
+    pub fn with_dot(&mut self, x: u32, y: u32, color: [u8; 4], radius: u32) -> &mut Self {
+        self.dots.push((x, y, color, radius));
+        self
+    }
+
+    /// Tints all points of a movement range, see :func:`Map.with_movement_range`
+    pub fn with_movement_range(
+        &mut self,
+        movement_range: &MovementRange,
+        color: [u8; 4],
+    ) -> &mut Self {
+        let points = movement_range.distances.keys().copied().collect();
+        self.overlays.push((points, color));
+        self
+    }
+
+    /// Draws the grid on the map, see :func:`Map.with_grid`
+    pub fn with_grid(&mut self) -> &mut Self {
+        self.should_draw_with_grid = true;
+        self
+    }
+
+    /// Draws the obstacles on the map, see :func:`Map.with_obstacles`
+    pub fn with_obstacles(&mut self) -> &mut Self {
+        self.draw_obstacles = true;
+        self
+    }
+
+    /// Sets the terrain cost layer used for pathfinding, see :func:`Map.with_cost_map`
+    pub fn with_cost_map(&mut self, cost_map: Vec<u8>) -> Result<&mut Self> {
+        if cost_map.len() != self.bytes.len() {
+            return Err(RpgMapError::SizeMismatch {
+                name: "Cost map",
+                expected: self.bytes.len(),
+                actual: cost_map.len(),
+            });
         }
+        let costs = cost_map
+            .chunks_exact(4)
+            .map(|pixel| {
+                if pixel[3] == 0 {
+                    return 1;
+                }
+                self.rgba_to_grayscale(&[pixel[0], pixel[1], pixel[2], pixel[3]])[0].max(1)
+            })
+            .collect();
+        self.set_cost_map(Some(costs));
+        Ok(self)
     }
 
-    /// Draws the background image at every transparent pixel
-    /// if the background is set
-    ///
-    /// Parameters
-    /// ----------
-    /// bytes : List[int]
-    ///     The bytes of the image.
-    /// background : Optional[List[int]]
-    ///     The bytes of the background of the image.
-    #[staticmethod]
-    pub fn draw_background(bytes: Vec<u8>, background: Vec<u8>) -> PyResult<Vec<u8>> {
-        if background.len() != bytes.len() {
-            return Err(pyo3::exceptions::PyValueError::new_err(
-                "Background image must have the same size as the map",
-            ));
+    /// Sets the clearance of paths from obstacles and edges, see :func:`Map.with_buffer`
+    pub fn with_buffer(
+        &mut self,
+        edges: u32,
+        obstacles: Option<u32>,
+        overrides: BTreeMap<usize, u32>,
+    ) -> Result<&mut Self> {
+        if let Some(index) = overrides.keys().find(|i| **i >= self.obstacles.len()) {
+            return Err(RpgMapError::invalid(format!(
+                "There is no obstacle with index {index}"
+            )));
         }
-        let mut bytes_clone = bytes.clone(); // We do not want to mutate the original bytes
-        for (i, chunk) in background.chunks_exact(4).enumerate() {
-            let index = i * 4;
-            if bytes_clone[index + 3] < TRANSPARENT_THRESHOLD {
-                bytes_clone[index..index + 4].copy_from_slice(chunk);
+        self.edge_buffer = edges;
+        self.obstacle_buffer = obstacles.unwrap_or(edges);
+        self.obstacle_buffers = overrides;
+        Ok(self)
+    }
+
+    /// Fades revealed areas into the fog over `width` pixels, see :func:`Map.with_fog_feather`
+    pub fn with_fog_feather(&mut self, width: u32) -> &mut Self {
+        self.fog_feather = width;
+        self
+    }
+
+    /// Covers hidden areas with a color, see :func:`Map.with_fog_color`
+    pub fn with_fog_color(&mut self, color: [u8; 4]) -> &mut Self {
+        self.fog = Fog::Color(color);
+        self
+    }
+
+    /// Covers hidden areas with a repeating texture, see :func:`Map.with_fog_texture`
+    pub fn with_fog_texture(
+        &mut self,
+        bytes: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> Result<&mut Self> {
+        if width == 0 || height == 0 {
+            return Err(RpgMapError::invalid(format!(
+                "The fog texture must be at least 1x1 pixels, got {width}x{height}"
+            )));
+        }
+        let expected = width as usize * height as usize * 4;
+        if bytes.len() != expected {
+            return Err(RpgMapError::invalid(format!(
+                "The fog texture must have {expected} bytes for a {width}x{height} image, but has {} bytes",
+                bytes.len()
+            )));
+        }
+        self.fog = Fog::Texture {
+            bytes,
+            width,
+            height,
+        };
+        Ok(self)
+    }
+
+    /// Covers hidden areas with a darkened and desaturated map, see :func:`Map.with_fog_shade`
+    pub fn with_fog_shade(&mut self, brightness: f32, saturation: f32) -> Result<&mut Self> {
+        for (name, value) in [("Brightness", brightness), ("Saturation", saturation)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(RpgMapError::invalid(format!(
+                    "{name} must be between 0 and 1, got {value}"
+                )));
             }
         }
+        self.fog = Fog::Shade {
+            brightness,
+            saturation,
+        };
+        Ok(self)
+    }
 
-        Ok(bytes_clone)
+    /// Sets how much fog covers explored areas of an explored map, see :func:`Map.with_explored_fog`
+    pub fn with_explored_fog(&mut self, opacity: f32) -> Result<&mut Self> {
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(RpgMapError::invalid(format!(
+                "Opacity must be between 0 and 1, got {opacity}"
+            )));
+        }
+        self.explored_fog = opacity;
+        Ok(self)
     }
 
-    /// Adds a dot do be drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
-    ///
-    /// Parameters
-    /// ----------
-    /// x : int
-    ///     The x coordinate of the dot.
-    /// y : int
-    ///     The y coordinate of the dot.
-    /// color : Tuple[int, int, int, int]
-    ///     The color of the dot.
-    /// radius : int
-    ///     The radius of the dot.
-    ///
-    /// Returns
-    /// -------
-    /// Map
-    ///     The map with the dot.
-    ///
-    pub fn with_dot(
-        mut slf: PyRefMut<'_, Self>,
-        x: u32,
-        y: u32,
-        color: [u8; 4],
-        radius: u32,
-    ) -> PyRefMut<'_, Self> {
-        slf.dots.push((x, y, color, radius));
-        slf
-    }
-
-    /// If called, a grid is drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
-    pub fn with_grid(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
-        slf.should_draw_with_grid = true;
-        slf
-    }
-
-    /// If called, the obstacles are drawn on the map when :func:`Map.full_image`, :func:`Map.masked_image` or :func:`Map.get_bits` is called
-    pub fn with_obstacles(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
-        slf.draw_obstacles = true;
-        slf
-    }
-
-    /// Clears all internal variables that may be set to true to start with a clean slate
-    pub fn clear_extras(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
-        slf.dots.clear();
-        slf.draw_obstacles = false;
-        slf.should_draw_with_grid = false;
-        slf
-    }
-
-    /// Takes in a coordinate, if it is close to an "unlocked" grid point it will unlock it and return true, if the point is already unlocked it will return false
-    ///
-    /// Parameters
-    /// ----------
-    /// x : int
-    ///     The x coordinate of the point to unlock.
-    /// y : int
-    ///     The y coordinate of the point to unlock.
-    ///
-    /// Returns
-    /// -------
-    /// bool
-    ///     True if the point was unlocked, False otherwise (already unlocked).
+    /// Only reveals what can be seen from the unlocked points, see :func:`Map.with_line_of_sight`
+    pub fn with_line_of_sight(&mut self, enabled: bool) -> &mut Self {
+        self.line_of_sight = enabled;
+        self
+    }
+
+    /// Sets the area revealed around a special point or unlocked grid point, see :func:`Map.with_reveal_shape`
+    pub fn with_reveal_shape(
+        &mut self,
+        point: (u32, u32),
+        shape: RevealShape,
+    ) -> Result<&mut Self> {
+        shape.validate(self.diagonal())?;
+        self.reveal_shapes.insert(point, shape);
+        Ok(self)
+    }
+
+    /// Defines a named region which can be revealed at once, see :func:`Map.with_region`
+    pub fn with_region(&mut self, name: &str, points: Vec<(u32, u32)>) -> Result<&mut Self> {
+        if points.len() < 3 {
+            return Err(RpgMapError::invalid(format!(
+                "A region needs at least 3 points, got {}",
+                points.len()
+            )));
+        }
+        if let Some(point) = points
+            .iter()
+            .find(|(x, y)| *x >= self.width || *y >= self.height)
+        {
+            return Err(RpgMapError::OutOfBounds {
+                point: *point,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        self.regions.insert(name.to_string(), points);
+        Ok(self)
+    }
+
+    /// Removes all dots, overlays, the grid and the obstacles drawn on the map
+    pub fn clear_extras(&mut self) -> &mut Self {
+        self.dots.clear();
+        self.overlays.clear();
+        self.draw_obstacles = false;
+        self.should_draw_with_grid = false;
+        self
+    }
+
+    /// Unlocks the grid point closest to a coordinate, see :func:`Map.unlock_point_from_coordinates`
     pub fn unlock_point_from_coordinates(&mut self, x: u32, y: u32) -> bool {
         let point = self.closest_to_point((x, y));
-        if self.unlocked.contains(&point) {
+        self.current = Some(point);
+        self.last_seen.insert(point, self.clock);
+        if self.exploration.contains(point) {
             return false;
         }
-        if self.map_type == MapType::Limited {
-            self.unlocked.push(point);
-        } else {
-            self.unlocked = vec![point]; // Only one point for a limited map
+        if !matches!(self.map_type, MapType::Limited | MapType::Explored) {
+            // Only one point for a limited map
+            self.unlocked.clear();
+            self.exploration.clear();
+        }
+        self.unlocked.push(point);
+        self.exploration.insert(point);
+        true
+    }
+
+    /// Hides the grid point closest to a coordinate again, see :func:`Map.lock_point_from_coordinates`
+    pub fn lock_point_from_coordinates(&mut self, x: u32, y: u32) -> bool {
+        let point = self.closest_to_point((x, y));
+        self.lock_point(point)
+    }
+
+    fn lock_point(&mut self, point: (u32, u32)) -> bool {
+        self.last_seen.remove(&point);
+        if self.current == Some(point) {
+            self.current = None;
+        }
+        if !self.exploration.remove(point) {
+            return false;
         }
+        self.unlocked.retain(|unlocked| *unlocked != point);
         true
     }
 
-    /// Draws the path from :func:`Travel.computed_path` on the image.
-    ///
-    /// Parameters
-    /// ----------
-    /// travel : Travel
-    ///     The travel object containing the path to draw.
-    /// percentage : float
-    ///     The percentage of the path to draw. 0.0 to 1.0.
-    /// line_width : int
-    ///     The width of the line to draw in pixels. Note that if the line has an outline the width will be this +2px
-    /// path_type : PathStyle
-    ///     The type of path to draw. Can be Solid, Dotted, SolidWithOutline or DottedWithOutline.
-    /// path_display : PathDisplayType
-    ///     The type of path display to use. Can be BelowMask or AboveMask.
-    ///
-    /// Returns
-    /// -------
-    /// List[int]
-    ///     The bytes of the image with the path drawn.
-    #[pyo3(signature = (
-        travel,
-        percentage,
-        line_width,
-        path_type = PathStyle::DottedWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
-        display_style = PathDisplayType::BelowMask,
-        progress_display_type = PathProgressDisplayType::Travelled
-    ))]
+    /// Sets after how many ticks unlocked points are hidden again, see :func:`Map.with_decay`
+    pub fn with_decay(&mut self, ticks: Option<u32>) -> &mut Self {
+        self.decay = ticks;
+        self
+    }
+
+    /// Advances the clock of the map and hides the points which decayed, see :func:`Map.tick`
+    pub fn tick(&mut self, ticks: u32) -> Vec<(u32, u32)> {
+        self.clock += ticks as u64;
+        let Some(decay) = self.decay else {
+            return Vec::new();
+        };
+        // Points unlocked without visiting them, like the ones the map was created with, were seen at tick 0
+        let mut forgotten = Vec::new();
+        self.unlocked.retain(|point| {
+            let seen = self.last_seen.get(point).copied().unwrap_or(0);
+            let decayed = self.clock - seen >= decay as u64;
+            if decayed {
+                forgotten.push(*point);
+            }
+            !decayed
+        });
+        for point in &forgotten {
+            self.exploration.remove(*point);
+            if self.current == Some(*point) {
+                self.current = None;
+            }
+        }
+        let exploration = &self.exploration;
+        self.last_seen
+            .retain(|point, _| exploration.contains(*point));
+        forgotten
+    }
+
+    /// Unlocks the grid point closest to a coordinate and reveals the shape around it,
+    /// see :func:`Map.unlock_point_from_coordinates`
+    pub fn unlock_point_with_shape(&mut self, x: u32, y: u32, shape: RevealShape) -> Result<bool> {
+        shape.validate(self.diagonal())?;
+        let unlocked = self.unlock_point_from_coordinates(x, y);
+        self.reveal_shapes
+            .insert(self.closest_to_point((x, y)), shape);
+        Ok(unlocked)
+    }
+
+    /// Reveals a region, see :func:`Map.unlock_region`
+    pub fn unlock_region(&mut self, name: &str) -> Result<bool> {
+        self.check_region(name)?;
+        Ok(self.unlocked_regions.insert(name.to_string()))
+    }
+
+    /// Hides a region again, see :func:`Map.lock_region`
+    pub fn lock_region(&mut self, name: &str) -> Result<bool> {
+        self.check_region(name)?;
+        Ok(self.unlocked_regions.remove(name))
+    }
+
+    fn check_region(&self, name: &str) -> Result<()> {
+        if !self.regions.contains_key(name) {
+            return Err(RpgMapError::invalid(format!(
+                "There is no region named {name:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The names of the unlocked regions
+    pub fn unlocked_regions(&self) -> impl Iterator<Item = &str> {
+        self.unlocked_regions.iter().map(String::as_str)
+    }
+
+    /// The unlocked grid points
+    pub fn unlocked(&self) -> &[(u32, u32)] {
+        &self.unlocked
+    }
+
+    /// The grid point which was unlocked last, which is the current position on an explored map
+    pub fn current_position(&self) -> Option<(u32, u32)> {
+        self.current
+    }
+
+    /// Encodes an image of the map as PNG, see :func:`Map.to_png_bytes`.
+    /// `bytes` default to the result of `get_bits`.
+    #[cfg(feature = "image")]
+    #[allow(clippy::wrong_self_convention)] // Drawing the map needs a mutable reference
+    pub fn to_png_bytes(&mut self, bytes: Option<Vec<u8>>) -> Result<Vec<u8>> {
+        let bytes = bytes.unwrap_or_else(|| self.get_bits());
+        encoding::encode_png(bytes, self.width, self.height)
+    }
+
+    /// Saves an image of the map as a PNG file, see :func:`Map.save_png`
+    #[cfg(feature = "image")]
+    pub fn save_png(&mut self, path: &std::path::Path, bytes: Option<Vec<u8>>) -> Result<()> {
+        let png = self.to_png_bytes(bytes)?;
+        Ok(std::fs::write(path, png)?)
+    }
+
+    /// Draws the background image at every transparent pixel, see :func:`Map.draw_background`
+    pub fn draw_background(bytes: Vec<u8>, background: Vec<u8>) -> Result<Vec<u8>> {
+        if background.len() != bytes.len() {
+            return Err(RpgMapError::SizeMismatch {
+                name: "Background image",
+                expected: bytes.len(),
+                actual: background.len(),
+            });
+        }
+        let mut bytes_clone = bytes.clone(); // We do not want to mutate the original bytes
+        for (i, chunk) in background.chunks_exact(4).enumerate() {
+            let index = i * 4;
+            if bytes_clone[index + 3] < TRANSPARENT_THRESHOLD {
+                bytes_clone[index..index + 4].copy_from_slice(chunk);
+            }
+        }
+
+        Ok(bytes_clone)
+    }
+
+    /// Draws the path of a travel on the image, see :func:`Map.draw_path`
     pub fn draw_path(
         &mut self,
         travel: Travel,
@@ -343,11 +866,11 @@ impl Map {
         path_type: PathStyle,
         display_style: PathDisplayType,
         progress_display_type: PathProgressDisplayType,
-    ) -> PyResult<Vec<u8>> {
+    ) -> Result<Vec<u8>> {
         self.should_draw_extras = false; // Extras should be drawn ABOVE the line
         self.line_width_checker(line_width, path_type)?;
         let distance = (line_width * 5) as usize;
-        let path = travel.computed_path.clone();
+        let path = travel.computed_path().to_vec();
         let critical_index = ((path.len() - 1) as f32 * percentage) as usize;
         let to_be_drawn: Vec<PathPoint> = match progress_display_type {
             PathProgressDisplayType::Remaining => path[critical_index..].to_vec(),
@@ -355,16 +878,16 @@ impl Map {
             PathProgressDisplayType::Progress => path,
         };
         // Unlock the points traversed so far
-        if self.map_type == MapType::Limited {
-            travel.computed_path[..=critical_index]
+        if matches!(self.map_type, MapType::Limited | MapType::Explored) {
+            travel.computed_path()[..=critical_index]
                 .iter()
                 .for_each(|point| {
                     self.unlock_point_from_coordinates(point.x, point.y);
                 });
         } else if self.map_type == MapType::Hidden {
             self.unlock_point_from_coordinates(
-                travel.computed_path[critical_index].x,
-                travel.computed_path[critical_index].y,
+                travel.computed_path()[critical_index].x,
+                travel.computed_path()[critical_index].y,
             );
         }
 
@@ -384,7 +907,7 @@ impl Map {
                 image,
                 *point,
                 &path_type,
-                travel.computed_path.clone(),
+                travel.computed_path().to_vec(),
                 pos,
                 distance,
                 line_width,
@@ -395,7 +918,7 @@ impl Map {
 
         match display_style {
             PathDisplayType::BelowMask => match self.map_type {
-                MapType::Hidden | MapType::Limited => {
+                MapType::Hidden | MapType::Limited | MapType::Explored => {
                     let masked = self.mask_image(image);
                     Ok(self.draw_extras(masked))
                 }
@@ -405,13 +928,8 @@ impl Map {
         }
     }
 
-    /// Returns the full image. If specified, draws the grid, obstacles, and dots.
-    ///
-    /// Returns
-    /// -------
-    /// List[int]
-    ///    The bytes of the image with the grid, obstacles, and dots drawn.
-    fn full_image(&mut self) -> Vec<u8> {
+    /// The full image with the extras drawn on it
+    pub fn full_image(&mut self) -> Vec<u8> {
         let mut image = self.bytes.clone();
         image = self.deal_with_transparent_pixels(image);
         if self.should_draw_extras {
@@ -420,13 +938,8 @@ impl Map {
         image
     }
 
-    /// Returns the masked image. If specified, draws the grid, obstacles, and dots.
-    ///
-    /// Returns
-    /// -------
-    /// List[int]
-    ///   The bytes of the image with the grid, obstacles, and dots drawn.
-    fn masked_image(&mut self) -> Vec<u8> {
+    /// The image with the mask of the unlocked points applied and the extras drawn on it
+    pub fn masked_image(&mut self) -> Vec<u8> {
         let mask = self.create_mask();
         let mut image = self.bytes.clone();
         image = self.deal_with_transparent_pixels(image);
@@ -437,23 +950,234 @@ impl Map {
         image
     }
 
-    /// The main method to get the image bytes.
-    /// Respects the map type and draws the grid, obstacles, and dots if specified.
-    ///
-    /// Returns
-    /// -------
-    /// List[int]
-    ///   The bytes of the image with the grid, obstacles, and dots drawn.
+    /// The image bytes, see :func:`Map.get_bits`
     pub fn get_bits(&mut self) -> Vec<u8> {
         match self.map_type {
             MapType::Full => self.full_image(),
-            MapType::Hidden | MapType::Limited => self.masked_image(),
+            MapType::Hidden | MapType::Limited | MapType::Explored => self.masked_image(),
         }
     }
-}
 
-// These methods are not exposed to the Python library
-impl Map {
+    /// Everything needed to restore the map
+    pub fn state(&self, include_bytes: bool) -> MapState {
+        MapState {
+            version: STATE_VERSION,
+            width: self.width,
+            height: self.height,
+            bytes: include_bytes.then(|| self.bytes.clone()),
+            bytes_hash: self.bytes_hash.clone(),
+            grid_size: self.grid_size,
+            unlocked: self.unlocked.clone(),
+            special_points: self.special_points.clone(),
+            obstacles: self.obstacles.clone(),
+            map_type: self.map_type,
+            draw_obstacles: self.draw_obstacles,
+            dots: self.dots.clone(),
+            overlays: self.overlays.clone(),
+            should_draw_with_grid: self.should_draw_with_grid,
+            should_draw_extras: self.should_draw_extras,
+            cost_map: self.cost_map.clone(),
+            edge_buffer: self.edge_buffer,
+            obstacle_buffer: self.obstacle_buffer,
+            obstacle_buffers: self.obstacle_buffers.clone(),
+            fog_feather: self.fog_feather,
+            fog: self.fog.clone(),
+            explored_fog: Some(self.explored_fog),
+            current: self.current,
+            line_of_sight: self.line_of_sight,
+            reveal_shapes: self
+                .reveal_shapes
+                .iter()
+                .map(|(point, shape)| (*point, shape.clone()))
+                .collect(),
+            regions: self.regions.clone(),
+            unlocked_regions: self.unlocked_regions.clone(),
+            decay: self.decay,
+            clock: self.clock,
+            last_seen: self
+                .last_seen
+                .iter()
+                .map(|(point, tick)| (*point, *tick))
+                .collect(),
+        }
+    }
+
+    /// Restores a map from its state. `bytes` are required if the state does not contain them.
+    pub fn from_state(state: MapState, bytes: Option<Vec<u8>>) -> Result<Map> {
+        let bytes = match (bytes, state.bytes) {
+            (Some(bytes), _) | (None, Some(bytes)) => bytes,
+            (None, None) => {
+                return Err(RpgMapError::invalid(
+                    "The map was serialized without its bytes, so they have to be passed in",
+                ))
+            }
+        };
+        if hash_bytes(&bytes) != state.bytes_hash {
+            return Err(RpgMapError::invalid(
+                "The bytes do not match the serialized map",
+            ));
+        }
+        let mut map = Map::new(
+            bytes,
+            state.width,
+            state.height,
+            state.grid_size,
+            state.map_type,
+            state.unlocked,
+            state.special_points,
+            state.obstacles,
+        )?;
+        map.draw_obstacles = state.draw_obstacles;
+        map.dots = state.dots;
+        map.overlays = state.overlays;
+        map.should_draw_with_grid = state.should_draw_with_grid;
+        map.should_draw_extras = state.should_draw_extras;
+        // The cost layer has one cost per pixel, or pathfinding would read past it
+        if let Some(costs) = &state.cost_map {
+            let expected = map.width as usize * map.height as usize;
+            if costs.len() != expected {
+                return Err(RpgMapError::SizeMismatch {
+                    name: "Cost map",
+                    expected,
+                    actual: costs.len(),
+                });
+            }
+        }
+        map.set_cost_map(state.cost_map);
+        map.with_buffer(
+            state.edge_buffer,
+            Some(state.obstacle_buffer),
+            state.obstacle_buffers,
+        )?;
+        map.fog_feather = state.fog_feather;
+        match state.fog {
+            // The texture has to match its size, or drawing the fog would read past it
+            Fog::Texture {
+                bytes,
+                width,
+                height,
+            } => {
+                map.with_fog_texture(bytes, width, height)?;
+            }
+            fog => map.fog = fog,
+        }
+        if let Some(explored_fog) = state.explored_fog {
+            map.with_explored_fog(explored_fog)?;
+        }
+        map.current = state.current;
+        map.line_of_sight = state.line_of_sight;
+        for (point, shape) in state.reveal_shapes {
+            map.with_reveal_shape(point, shape)?;
+        }
+        for (name, points) in state.regions {
+            map.with_region(&name, points)?;
+        }
+        for name in state.unlocked_regions {
+            map.unlock_region(&name)?;
+        }
+        // Points cannot have been seen after the current tick, or decaying them would underflow
+        if let Some((point, seen)) = state.last_seen.iter().find(|(_, seen)| *seen > state.clock) {
+            return Err(RpgMapError::invalid(format!(
+                "Point {point:?} was last seen at tick {seen}, after the current tick {}",
+                state.clock
+            )));
+        }
+        map.decay = state.decay;
+        map.clock = state.clock;
+        map.last_seen = state.last_seen.into_iter().collect();
+        Ok(map)
+    }
+
+    /// The width of the map in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the map in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The length of the diagonal of the map in pixels, rounded up
+    fn diagonal(&self) -> u32 {
+        (self.width as f64).hypot(self.height as f64).ceil() as u32
+    }
+
+    /// The raw bytes of the image the map was created with
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The obstacles of the map as polygons
+    pub fn obstacles(&self) -> &[Vec<(u32, u32)>] {
+        &self.obstacles
+    }
+
+    /// Returns the navigation grid of the map. It is only rebuilt if the bytes, obstacles,
+    /// buffers, map type or cost layer changed since it was last built.
+    pub fn navigation_grid(&self) -> Arc<NavigationGrid> {
+        let fingerprint = self.navigation_fingerprint();
+        let mut cache = self
+            .navigation
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(grid) = cache.as_ref() {
+            if grid.fingerprint == fingerprint {
+                return grid.clone();
+            }
+        }
+        let grid = Arc::new(NavigationGrid::new(self, fingerprint));
+        *cache = Some(grid.clone());
+        grid
+    }
+
+    /// The movement cost of every pixel, row by row, if the map has a cost layer
+    pub fn cost_map(&self) -> Option<&[u8]> {
+        self.cost_map.as_deref()
+    }
+
+    /// The clearance paths keep from transparent pixels
+    pub fn edge_buffer(&self) -> u32 {
+        self.edge_buffer
+    }
+
+    /// The clearance paths keep from obstacles without their own buffer
+    pub fn obstacle_buffer(&self) -> u32 {
+        self.obstacle_buffer
+    }
+
+    /// The clearance of single obstacles by their index, see :func:`Map.with_buffer`
+    pub fn obstacle_buffers(&self) -> &BTreeMap<usize, u32> {
+        &self.obstacle_buffers
+    }
+
+    /// The clearance paths keep from the obstacle with the given index
+    pub fn buffer_of_obstacle(&self, index: usize) -> u32 {
+        *self
+            .obstacle_buffers
+            .get(&index)
+            .unwrap_or(&self.obstacle_buffer)
+    }
+
+    /// Sets the cost layer and hashes it for the navigation fingerprint
+    fn set_cost_map(&mut self, cost_map: Option<Vec<u8>>) {
+        self.cost_map_hash = hash_value(&cost_map);
+        self.cost_map = cost_map;
+    }
+
+    /// Hashes everything the navigation grid is built from. The terrain and the cost layer
+    /// are large, so they were hashed once when they were set and only their hashes are used.
+    fn navigation_fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.terrain_hash.hash(&mut hasher);
+        self.map_type.hash(&mut hasher);
+        self.cost_map_hash.hash(&mut hasher);
+        self.edge_buffer.hash(&mut hasher);
+        self.obstacle_buffer.hash(&mut hasher);
+        self.obstacle_buffers.hash(&mut hasher);
+        hasher.finish()
+    }
+
     fn deal_with_transparent_pixels(&self, mut image: Vec<u8>) -> Vec<u8> {
         for chunk in image.chunks_exact_mut(4) {
             if chunk[3] < TRANSPARENT_THRESHOLD {
@@ -463,9 +1187,10 @@ impl Map {
         image
     }
 
-    /// Draw any extras on the image including obstacles, dots, and the grid
+    /// Draw any extras on the image including obstacles, overlays, dots, and the grid
     fn draw_extras(&mut self, mut image: Vec<u8>) -> Vec<u8> {
         image = self.draw_obstacles(image);
+        image = self.draw_overlays(image);
         image = self.draw_dots(image);
         image = self.draw_with_grid(image);
         image
@@ -478,25 +1203,19 @@ impl Map {
             .find(|p| self.closest_to_point(**p) == self.closest_to_point((x, y)))
     }
 
-    /// Does some checks if the line width is too small
-    fn line_width_checker(&self, line_width: i32, style: PathStyle) -> PyResult<()> {
-        if line_width < 1 {
-            return Err(pyo3::exceptions::PyValueError::new_err(
-                "Line width must be at least 1",
-            ));
-        }
-        if line_width > self.grid_size as i32 {
-            return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Line width must be less than the grid size {}",
-                self.grid_size
-            )));
-        }
-        if let PathStyle::SolidWithOutline(_, _) | PathStyle::DottedWithOutline(_, _) = style {
-            if line_width < 2 {
-                return Err(pyo3::exceptions::PyValueError::new_err(
-                    "Line width must be at least 2 for outline",
-                ));
-            }
+    /// Checks if the line width is at least 1 (2 for outlines) and at most the grid size
+    fn line_width_checker(&self, line_width: i32, style: PathStyle) -> Result<()> {
+        let min = match style {
+            PathStyle::SolidWithOutline(_, _) | PathStyle::DottedWithOutline(_, _) => 2,
+            _ => 1,
+        };
+        let max = self.grid_size as i32;
+        if line_width < min || line_width > max {
+            return Err(RpgMapError::InvalidStyle {
+                line_width,
+                min,
+                max,
+            });
         }
         Ok(())
     }
@@ -518,60 +1237,49 @@ impl Map {
 
     /// Finds the closest grid point with the given coordinates
     fn closest_to_point(&self, point: (u32, u32)) -> (u32, u32) {
-        let mut min_dist = u32::MAX;
-        let mut closest_point = (0, 0);
-
-        for p in &self.grid_points {
-            let dist = (p.0 as i32 - point.0 as i32).unsigned_abs()
-                + (p.1 as i32 - point.1 as i32).unsigned_abs();
-            if dist < min_dist {
-                min_dist = dist;
-                closest_point = *p;
-            }
-        }
-
-        closest_point
+        self.exploration.closest(point)
     }
 
-    /// Turns every pixel of the image black where the mask is not transparent
+    /// Covers every pixel of the image with the fog where the mask is not transparent
     fn put_mask_on_image(&self, mut image: Vec<u8>, mask: Vec<u8>) -> Vec<u8> {
         for (i, chunk) in mask.chunks_exact(4).enumerate() {
             let a = chunk[3];
-            if a != 0 {
-                let index = i * 4;
-                image[index..index + 4].copy_from_slice(&[0, 0, 0, 255]);
+            if a == 0 {
+                continue;
+            }
+            let index = i * 4;
+            let pixel = &mut image[index..index + 4];
+            let x = i as u32 % self.width;
+            let y = i as u32 / self.width;
+            let color = self.fog.color_at(x, y, pixel);
+            if a == 255 && color[3] == 255 {
+                pixel.copy_from_slice(&color);
+                continue;
+            }
+            // Feathered edge or translucent fog, blend the pixel with the fog
+            let fog = a as f32 / 255.0 * color[3] as f32 / 255.0;
+            for channel in 0..3 {
+                pixel[channel] = (pixel[channel] as f32 * (1.0 - fog) + color[channel] as f32 * fog)
+                    .round() as u8;
             }
+            pixel[3] = (pixel[3] as f32 * (1.0 - fog) + 255.0 * fog).round() as u8;
         }
         image
     }
 
-    /// Helper function to check if four points form a square
-    fn is_square(&mut self, points: Vec<(u32, u32)>) -> bool {
-        let mut sorted = points.clone();
-        sorted.sort(); // Sort by x, then y
-
-        let (x1, y1) = sorted[0];
-        let (x4, y4) = sorted[3];
-
-        let side1 = (x4 as i32 - x1 as i32).abs();
-        let side2 = (y4 as i32 - y1 as i32).abs();
-
-        side1 == side2 && side1 > 0 // Check if square with non-zero side length
-    }
-
-    /// Helper function to make everything inside a square transparent
-    fn make_square_transparent(&mut self, mut mask: Vec<u8>, points: Vec<(u32, u32)>) -> Vec<u8> {
-        let mut sorted = points.clone();
-        sorted.sort(); // Sort by x, then y
-
-        let (x_min, y_min) = sorted[0];
-        let (x_max, y_max) = sorted[3];
-
+    /// Helper function to cover everything inside a grid cell with at most `fog`
+    fn make_square_transparent(
+        &self,
+        mut mask: Vec<u8>,
+        (x_min, y_min): (u32, u32),
+        (x_max, y_max): (u32, u32),
+        fog: u8,
+    ) -> Vec<u8> {
         for y in y_min..=y_max {
             for x in x_min..=x_max {
                 if x < self.width && y < self.height {
                     let index = (y * self.width + x) as usize * 4;
-                    mask[index + 3] = 0; // Transparent
+                    mask[index + 3] = mask[index + 3].min(fog);
                 }
             }
         }
@@ -580,82 +1288,145 @@ impl Map {
     }
 
     /// Creates a mask for the map, taking into account the unlocked points
-    /// and transparent background
+    /// and transparent background. The alpha value of the mask is how much
+    /// fog covers a pixel, which is in between 0 and 255 at feathered edges
+    /// and in explored areas.
     fn create_mask(&mut self) -> Vec<u8> {
         let mut mask = self.bytes.clone();
-
-        for (mut cx, mut cy) in &self.unlocked {
-            let radius: i32;
-            if let Some((x, y)) = self.is_special_point(cx, cy) {
-                cx = *x;
-                cy = *y;
-                radius = ((self.grid_size as f32) / 0.3) as i32;
-            } else {
-                radius = ((self.grid_size as f32) / 0.8) as i32;
+        for chunk in mask.chunks_exact_mut(4) {
+            if chunk[3] != 0 {
+                chunk[3] = 255;
             }
-            let cx = cx as i32;
-            let cy = cy as i32;
-            let radius_sq = radius * radius;
-            for dy in -radius..=radius {
-                for dx in -radius..=radius {
-                    let x = cx + dx;
-                    let y = cy + dy;
+        }
 
-                    // Check if the point is within the circle radius
-                    if dx * dx + dy * dy <= radius_sq {
-                        // Ensure the pixel is within bounds
-                        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
-                            let index = (y * self.width as i32 + x) as usize * 4;
-                            mask[index + 3] = 0; // Make it transparent
-                        }
-                    }
+        // Regions count as explored on an explored map, they are not in view
+        let region_fog = match self.map_type {
+            MapType::Explored => (self.explored_fog * 255.0).round() as u8,
+            _ => 0,
+        };
+        for name in &self.unlocked_regions {
+            mask = self.reveal_region(mask, &self.regions[name], region_fog);
+        }
+
+        let unlocked = self.unlocked.clone();
+        let occluders = self.line_of_sight.then(|| vision::occluders(self));
+        let occluders = occluders.as_deref();
+        match self.map_type {
+            MapType::Explored => {
+                let explored_fog = (self.explored_fog * 255.0).round() as u8;
+                mask = self.reveal(mask, &unlocked, explored_fog, occluders);
+                mask = self.reveal_cells(mask, explored_fog, occluders.is_some());
+                match self.current {
+                    Some(current) => self.reveal(mask, &[current], 0, occluders),
+                    None => mask, // The current position was locked
                 }
             }
+            _ => {
+                mask = self.reveal(mask, &unlocked, 0, occluders);
+                self.reveal_cells(mask, 0, occluders.is_some())
+            }
         }
+    }
 
-        // If the radius is larger than diagonal length of a square,
-        // we can stop and return here since the field would already be revealed
-        let smallest_radius = ((self.grid_size as f32) / 0.8) as i32;
-        if smallest_radius > (2.0_f32.sqrt() * self.grid_size as f32) as i32 {
+    /// Reveals the grid cells with all four corners unlocked, leaving at most `fog` on the mask.
+    /// Cells are not revealed with line of sight, they could be behind a wall.
+    fn reveal_cells(&self, mut mask: Vec<u8>, fog: u8, line_of_sight: bool) -> Vec<u8> {
+        if line_of_sight {
             return mask;
         }
+        for (top_left, bottom_right) in self.exploration.unlocked_cells() {
+            mask = self.make_square_transparent(mask, top_left, bottom_right, fog);
+        }
+        mask
+    }
 
-        let len = self.unlocked.len();
-        for i in 0..len {
-            let (x1, y1) = self.unlocked[i];
-
-            for j in i + 1..len {
-                let (x2, y2) = self.unlocked[j];
-
-                if (x1 as i32 - x2 as i32).abs() > self.grid_size as i32
-                    || (y1 as i32 - y2 as i32).abs() > self.grid_size as i32
-                {
-                    continue; // Skip if too far apart
+    /// Reveals everything inside the polygon of a region, leaving at most `fog` on the mask
+    fn reveal_region(&self, mut mask: Vec<u8>, points: &[(u32, u32)], fog: u8) -> Vec<u8> {
+        let polygon = to_polygon(points.iter().map(|&(x, y)| (x as f64, y as f64)));
+        // Only the bounding box of the polygon can contain it
+        let max_x = points.iter().map(|p| p.0).max().unwrap_or(0);
+        let max_y = points.iter().map(|p| p.1).max().unwrap_or(0);
+        let min_x = points.iter().map(|p| p.0).min().unwrap_or(0);
+        let min_y = points.iter().map(|p| p.1).min().unwrap_or(0);
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let point = Point::new(x as f64, y as f64);
+                if !polygon.contains(&point) && !polygon.exterior().contains(&point) {
+                    continue;
                 }
+                let index = (y * self.width + x) as usize * 4;
+                // Without a feather the distance to the edge does not matter
+                let depth = match self.fog_feather {
+                    0 => f32::INFINITY,
+                    _ => distance_to_edge(&polygon, point) as f32,
+                };
+                let fog = if depth >= self.fog_feather as f32 {
+                    fog
+                } else {
+                    let feather = 1.0 - depth / self.fog_feather as f32;
+                    (fog as f32 + (255 - fog) as f32 * feather).round() as u8
+                };
+                mask[index + 3] = mask[index + 3].min(fog);
+            }
+        }
+        mask
+    }
 
-                for k in j + 1..len {
-                    let (x3, y3) = self.unlocked[k];
-
-                    if (x1 as i32 - x3 as i32).abs() > self.grid_size as i32
-                        || (y1 as i32 - y3 as i32).abs() > self.grid_size as i32
-                    {
-                        continue;
-                    }
-
-                    for l in k + 1..len {
-                        let (x4, y4) = self.unlocked[l];
-
-                        if (x1 as i32 - x4 as i32).abs() > self.grid_size as i32
-                            || (y1 as i32 - y4 as i32).abs() > self.grid_size as i32
-                        {
-                            continue;
+    /// Reveals the area around the points on the mask, leaving at most `fog` on it.
+    /// With `occluders`, only the pixels visible from the points are revealed.
+    fn reveal(
+        &mut self,
+        mut mask: Vec<u8>,
+        points: &[(u32, u32)],
+        fog: u8,
+        occluders: Option<&[Vec<u8>]>,
+    ) -> Vec<u8> {
+        for &(x, y) in points {
+            // The shape of the unlocked point itself comes first, then that of its special point
+            let ((cx, cy), shape) =
+                match (self.reveal_shapes.get(&(x, y)), self.is_special_point(x, y)) {
+                    (Some(shape), _) => ((x, y), shape.clone()),
+                    (None, Some(&special)) => match self.reveal_shapes.get(&special) {
+                        Some(shape) => (special, shape.clone()),
+                        None => (
+                            special,
+                            RevealShape::Circle(((self.grid_size as f32) / 0.3) as u32),
+                        ),
+                    },
+                    (None, None) => (
+                        (x, y),
+                        RevealShape::Circle(((self.grid_size as f32) / 0.8) as u32),
+                    ),
+                };
+            let radius = shape.extent() as i32;
+            let depth = shape.depth();
+            let cx = cx as i32;
+            let cy = cy as i32;
+            let visible =
+                occluders.map(|occluders| vision::visible_area(occluders, (cx, cy), radius));
+            let side = (2 * radius + 1) as usize;
+            // Only the part of the shape which is on the map
+            let (min_dx, max_dx) = (radius.min(cx), radius.min(self.width as i32 - 1 - cx));
+            let (min_dy, max_dy) = (radius.min(cy), radius.min(self.height as i32 - 1 - cy));
+            for dy in -min_dy..=max_dy {
+                for dx in -min_dx..=max_dx {
+                    if let Some(visible) = &visible {
+                        if !visible[(dy + radius) as usize * side + (dx + radius) as usize] {
+                            continue; // Hidden behind an occluder
                         }
+                    }
 
-                        // Check if these four points form a square
-                        let points = vec![(x1, y1), (x2, y2), (x3, y3), (x4, y4)];
-                        if self.is_square(points.clone()) {
-                            mask = self.make_square_transparent(mask, points);
-                        }
+                    // Check if the point is within the shape
+                    if let Some(depth) = depth(dx, dy) {
+                        let index = ((cy + dy) * self.width as i32 + cx + dx) as usize * 4;
+                        // Pixels further than the feather from the edge are fully revealed
+                        let fog = if depth >= self.fog_feather as f32 {
+                            fog // Make it transparent
+                        } else {
+                            let feather = 1.0 - depth / self.fog_feather as f32;
+                            (fog as f32 + (255 - fog) as f32 * feather).round() as u8
+                        };
+                        mask[index + 3] = mask[index + 3].min(fog);
                     }
                 }
             }
@@ -664,6 +1435,25 @@ impl Map {
         mask
     }
 
+    /// Tints all points of the overlays defined in the `overlays` vector on the image
+    fn draw_overlays(&mut self, mut bytes: Vec<u8>) -> Vec<u8> {
+        for (points, color) in &self.overlays {
+            let opacity = color[3] as f32 / 255.0;
+            for (x, y) in points {
+                if *x >= self.width || *y >= self.height {
+                    continue;
+                }
+                let index = (y * self.width + x) as usize * 4;
+                for channel in 0..3 {
+                    bytes[index + channel] = (bytes[index + channel] as f32 * (1.0 - opacity)
+                        + color[channel] as f32 * opacity)
+                        .round() as u8;
+                }
+            }
+        }
+        bytes
+    }
+
     /// Draws all dots defined in the `dots` vector on the image
     fn draw_dots(&mut self, mut bytes: Vec<u8>) -> Vec<u8> {
         for (x, y, color, radius) in &self.dots {
@@ -781,7 +1571,7 @@ impl Map {
     /// Sets up the image for a path to be drawn on it
     fn setup_image_for_path(&mut self, display_style: PathDisplayType) -> Vec<u8> {
         match self.map_type {
-            MapType::Hidden | MapType::Limited => {
+            MapType::Hidden | MapType::Limited | MapType::Explored => {
                 if display_style == PathDisplayType::AboveMask {
                     self.masked_image()
                 } else {
@@ -934,6 +1724,13 @@ impl Map {
         let x = point.x as usize;
         let y = point.y as usize;
         let i = y * self.width as usize + x;
+        // A path of a single point, e.g. a travel to where it started, has neither neighbour
+        let straight_to_previous = pos
+            .checked_sub(1)
+            .is_none_or(|previous| !self.is_diagonal_to(point, path[previous]));
+        let straight_to_next = path
+            .get(pos + 1)
+            .is_none_or(|next| !self.is_diagonal_to(point, *next));
         match path_type {
             PathStyle::Debug() => {
                 let chunk = &mut image[i * 4..(i + 1) * 4];
@@ -941,8 +1738,7 @@ impl Map {
             }
             PathStyle::Solid(color) => {
                 let color = self.color_helper(*color, progress_display_type, pos, critical_index);
-                if (pos == 0 && !self.is_diagonal_to(point, path[pos + 1]))
-                    || (pos == path.len() - 1 && !self.is_diagonal_to(point, path[pos - 1]))
+                if (pos == 0 && straight_to_next) || (pos == path.len() - 1 && straight_to_previous)
                 {
                     image = self.endpoint_helper(image, point, line_width, color, color);
                 } else {
@@ -951,10 +1747,10 @@ impl Map {
             }
             PathStyle::Dotted(color) => {
                 let color = self.color_helper(*color, progress_display_type, pos, critical_index);
-                if ((pos == path.len() - 1 || (pos - 1) / 10 % (distance / 10 + 1) == 0)
-                    && !self.is_diagonal_to(point, path[pos - 1]))
-                    || ((pos == 0 || (pos + 1) / 10 % (distance / 10 + 1) == 0)
-                        && !self.is_diagonal_to(point, path[pos + 1]))
+                if ((pos == path.len() - 1 || ((pos - 1) / 10).is_multiple_of(distance / 10 + 1))
+                    && straight_to_previous)
+                    || ((pos == 0 || ((pos + 1) / 10).is_multiple_of(distance / 10 + 1))
+                        && straight_to_next)
                 {
                     image = self.endpoint_helper(image, point, line_width, color, color);
                 } else {
@@ -965,8 +1761,7 @@ impl Map {
                 let color = self.color_helper(*color, progress_display_type, pos, critical_index);
                 let outline =
                     self.color_helper(*outline, progress_display_type, pos, critical_index);
-                if (pos == 0 && !self.is_diagonal_to(point, path[pos + 1]))
-                    || (pos == path.len() - 1 && !self.is_diagonal_to(point, path[pos - 1]))
+                if (pos == 0 && straight_to_next) || (pos == path.len() - 1 && straight_to_previous)
                 {
                     image = self.endpoint_helper(image, point, line_width, color, outline);
                 } else {
@@ -977,10 +1772,10 @@ impl Map {
                 let color = self.color_helper(*color, progress_display_type, pos, critical_index);
                 let outline =
                     self.color_helper(*outline, progress_display_type, pos, critical_index);
-                if ((pos == path.len() - 1 || (pos - 1) / 10 % (distance / 10 + 1) == 0)
-                    && !self.is_diagonal_to(point, path[pos - 1]))
-                    || ((pos == 0 || (pos + 1) / 10 % (distance / 10 + 1) == 0)
-                        && !self.is_diagonal_to(point, path[pos + 1]))
+                if ((pos == path.len() - 1 || ((pos - 1) / 10).is_multiple_of(distance / 10 + 1))
+                    && straight_to_previous)
+                    || ((pos == 0 || ((pos + 1) / 10).is_multiple_of(distance / 10 + 1))
+                        && straight_to_next)
                 {
                     image = self.endpoint_helper(image, point, line_width, color, outline);
                 } else {
@@ -1,3 +1,11 @@
+#[cfg(feature = "image")]
+pub mod encoding;
+pub mod error;
+pub mod exploration;
+pub mod hierarchy;
 pub mod map;
+pub mod navigation;
 pub mod path;
+pub mod state;
 pub mod travel;
+pub mod vision;
@@ -0,0 +1,284 @@
+use crate::structs::hierarchy::{Hierarchy, CLUSTER_SIZE};
+use crate::structs::map::{Map, MapType, TRANSPARENT_THRESHOLD};
+use geo::{Contains, Coord, LineString, Point, Polygon};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// The grid a map is reduced to for pathfinding, together with its movement costs.
+/// Building it is expensive, so a map caches it and only rebuilds it
+/// once its bytes, obstacles, buffers, map type or cost layer change.
+pub struct NavigationGrid {
+    /// 0 is a free space and 1 is an obstacle
+    pub grid: Vec<Vec<u8>>,
+    /// The movement cost of every cell, if the map has a cost layer
+    pub costs: Option<Vec<Vec<u8>>>,
+    /// Fingerprint of the map state the grid was built from
+    pub fingerprint: u64,
+    /// The connected region every cell belongs to, computed on first use
+    regions: OnceLock<Vec<Vec<u32>>>,
+    /// The portal graph for hierarchical pathfinding, computed on first use
+    hierarchy: OnceLock<Hierarchy>,
+}
+
+/// The cached navigation grid of a map. It is shared between clones
+/// of a map, so every `Travel` computed on the same map reuses it.
+pub type NavigationCache = Arc<Mutex<Option<Arc<NavigationGrid>>>>;
+
+impl NavigationGrid {
+    pub fn new(map: &Map, fingerprint: u64) -> Self {
+        NavigationGrid {
+            grid: image_to_grid(map),
+            costs: image_to_costs(map),
+            fingerprint,
+            regions: OnceLock::new(),
+            hierarchy: OnceLock::new(),
+        }
+    }
+
+    /// Labels every cell with the connected region it belongs to.
+    /// Obstacles are 0, free cells which can reach each other share the same label starting at 1.
+    pub fn regions(&self) -> &Vec<Vec<u32>> {
+        self.regions.get_or_init(|| label_regions(&self.grid))
+    }
+
+    /// Whether the regions were labelled already
+    #[cfg(test)]
+    pub fn has_regions(&self) -> bool {
+        self.regions.get().is_some()
+    }
+
+    /// The clusters and portals used for hierarchical pathfinding
+    pub fn hierarchy(&self) -> &Hierarchy {
+        self.hierarchy
+            .get_or_init(|| Hierarchy::new(&self.grid, self.costs.as_deref(), CLUSTER_SIZE))
+    }
+
+    /// The region a point is in, or None if it is an obstacle
+    pub fn region_of(&self, point: (u32, u32)) -> Option<u32> {
+        match self.regions()[point.1 as usize][point.0 as usize] {
+            0 => None,
+            region => Some(region),
+        }
+    }
+
+    /// Checks if there is a path between two points without searching for it
+    pub fn is_reachable(&self, from: (u32, u32), to: (u32, u32)) -> bool {
+        self.region_of(from)
+            .is_some_and(|region| self.region_of(to) == Some(region))
+    }
+
+    /// Finds the free cell which can be reached from `from` and is closest to `to`.
+    /// Returns None if `from` is an obstacle.
+    pub fn closest_reachable(&self, from: (u32, u32), to: (u32, u32)) -> Option<(u32, u32)> {
+        let region = self.region_of(from)?;
+        let distance = |x: usize, y: usize| {
+            let dx = x as i64 - to.0 as i64;
+            let dy = y as i64 - to.1 as i64;
+            dx * dx + dy * dy
+        };
+
+        let mut closest = from;
+        let mut min_distance = distance(from.0 as usize, from.1 as usize);
+        for (y, row) in self.regions().iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == region && distance(x, y) < min_distance {
+                    min_distance = distance(x, y);
+                    closest = (x as u32, y as u32);
+                }
+            }
+        }
+        Some(closest)
+    }
+}
+
+/// Flood fills every group of free cells which are connected
+/// (including diagonally, like the paths) with its own label
+fn label_regions(grid: &[Vec<u8>]) -> Vec<Vec<u32>> {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, |row| row.len());
+    let mut regions = vec![vec![0; cols]; rows];
+    let mut next_region = 1;
+    let mut stack = vec![];
+
+    for y in 0..rows {
+        for x in 0..cols {
+            if grid[y][x] == 1 || regions[y][x] != 0 {
+                continue;
+            }
+            regions[y][x] = next_region;
+            stack.push((x, y));
+            while let Some((cx, cy)) = stack.pop() {
+                for ny in cy.saturating_sub(1)..(cy + 2).min(rows) {
+                    for nx in cx.saturating_sub(1)..(cx + 2).min(cols) {
+                        if grid[ny][nx] != 1 && regions[ny][nx] == 0 {
+                            regions[ny][nx] = next_region;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+            }
+            next_region += 1;
+        }
+    }
+
+    regions
+}
+
+/// Give all 1s a X px "buffer" of 1s around them.
+/// The buffer is built from a distance transform, so its cost
+/// does not grow with the buffer size
+fn buffer_edges(reduced: Vec<Vec<u8>>, buffer_size: u32) -> Vec<Vec<u8>> {
+    if buffer_size == 0 {
+        return reduced;
+    }
+    let distances = chessboard_distances(&reduced);
+    distances
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&distance| (distance as u32 <= buffer_size) as u8)
+                .collect()
+        })
+        .collect()
+}
+
+/// Calculates the chessboard distance of every cell to the closest 1.
+/// Uses the classic two pass algorithm: the first pass carries distances
+/// down and right, the second one up and left.
+fn chessboard_distances(grid: &[Vec<u8>]) -> Vec<Vec<u16>> {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, |row| row.len());
+    let mut distances: Vec<Vec<u16>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&cell| if cell == 1 { 0 } else { u16::MAX })
+                .collect()
+        })
+        .collect();
+
+    for y in 0..rows {
+        for x in 0..cols {
+            let mut distance = distances[y][x];
+            if x > 0 {
+                distance = distance.min(distances[y][x - 1].saturating_add(1));
+            }
+            if y > 0 {
+                let above = &distances[y - 1][x.saturating_sub(1)..(x + 2).min(cols)];
+                if let Some(closest) = above.iter().min() {
+                    distance = distance.min(closest.saturating_add(1));
+                }
+            }
+            distances[y][x] = distance;
+        }
+    }
+
+    for y in (0..rows).rev() {
+        for x in (0..cols).rev() {
+            let mut distance = distances[y][x];
+            if x + 1 < cols {
+                distance = distance.min(distances[y][x + 1].saturating_add(1));
+            }
+            if y + 1 < rows {
+                let below = &distances[y + 1][x.saturating_sub(1)..(x + 2).min(cols)];
+                if let Some(closest) = below.iter().min() {
+                    distance = distance.min(closest.saturating_add(1));
+                }
+            }
+            distances[y][x] = distance;
+        }
+    }
+
+    distances
+}
+
+/// Converts the image to a grid where 0 is a free space and 1 is an obstacle
+pub fn image_to_grid(map: &Map) -> Vec<Vec<u8>> {
+    let empty = vec![vec![0; (map.width()) as usize]; (map.height()) as usize];
+    // Obstacles are collected in one layer per buffer size, so each layer can be buffered separately
+    let mut layers: BTreeMap<u32, Vec<Vec<u8>>> = BTreeMap::new();
+
+    let threshold = transparent_threshold(map.map_type);
+    let edges = layers
+        .entry(map.edge_buffer())
+        .or_insert_with(|| empty.clone());
+    for (i, byte) in map.bytes().chunks_exact(4).enumerate() {
+        let x = i % map.width() as usize;
+        let y = i / map.width() as usize;
+        let alpha = byte[3]; // Alpha channel
+        if alpha < threshold {
+            edges[y][x] = 1; // Transparent pixels -> Obstacle
+        }
+    }
+
+    // Step 2: Process polygon obstacles
+    for (i, obstacle) in map.obstacles().iter().enumerate() {
+        if obstacle.len() < 3 {
+            continue; // Skip invalid polygons
+        }
+        let layer = layers
+            .entry(map.buffer_of_obstacle(i))
+            .or_insert_with(|| empty.clone());
+        fill_polygon(layer, obstacle);
+    }
+
+    // Step 3: Buffer edges of obstacles
+    let mut grid = empty;
+    for (buffer_size, layer) in layers {
+        let buffered = buffer_edges(layer, buffer_size);
+        for (row, buffered_row) in grid.iter_mut().zip(buffered) {
+            for (cell, buffered_cell) in row.iter_mut().zip(buffered_row) {
+                *cell |= buffered_cell;
+            }
+        }
+    }
+
+    grid
+}
+
+/// Pixels with an alpha below this are treated as walls.
+/// The mask of hidden maps paints barely transparent pixels black,
+/// so only fully transparent pixels are obstacles there.
+pub fn transparent_threshold(map_type: MapType) -> u8 {
+    match map_type {
+        MapType::Full => TRANSPARENT_THRESHOLD,
+        MapType::Hidden | MapType::Limited | MapType::Explored => 1,
+    }
+}
+
+/// Marks every cell of the grid inside the polygon with 1
+pub fn fill_polygon(grid: &mut [Vec<u8>], obstacle: &[(u32, u32)]) {
+    let exterior = obstacle
+        .iter()
+        .map(|&coords| Coord {
+            x: coords.0 as f64,
+            y: coords.1 as f64,
+        })
+        .collect::<Vec<Coord>>();
+    let polygon = Polygon::new(LineString::from(exterior), vec![]);
+
+    // Only the bounding box of the polygon can contain it
+    let height = grid.len() as u32;
+    let width = grid.first().map_or(0, |row| row.len()) as u32;
+    let max_x = obstacle.iter().map(|p| p.0).max().unwrap_or(0);
+    let max_y = obstacle.iter().map(|p| p.1).max().unwrap_or(0);
+    let min_x = obstacle.iter().map(|p| p.0).min().unwrap_or(0);
+    let min_y = obstacle.iter().map(|p| p.1).min().unwrap_or(0);
+    for y in min_y..=max_y.min(height.saturating_sub(1)) {
+        for x in min_x..=max_x.min(width.saturating_sub(1)) {
+            if polygon.contains(&Point::new(x as f64, y as f64)) {
+                grid[y as usize][x as usize] = 1; // Mark obstacle
+            }
+        }
+    }
+}
+
+/// Converts the cost layer of the map (if any) to a grid of movement costs
+pub fn image_to_costs(map: &Map) -> Option<Vec<Vec<u8>>> {
+    map.cost_map().map(|costs| {
+        costs
+            .chunks_exact(map.width() as usize)
+            .map(|row| row.to_vec())
+            .collect()
+    })
+}
@@ -1,19 +1,21 @@
-use pyo3::prelude::pyclass;
-use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap};
 use workaround::stubgen;
 
 #[stubgen]
-#[pyclass]
-#[derive(Clone, PartialEq, Eq, Debug, Copy)]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map"))]
+#[derive(Clone, PartialEq, Eq, Debug, Copy, Serialize, Deserialize)]
 pub struct PathPoint {
-    #[pyo3(get)]
     pub x: u32,
-    #[pyo3(get)]
     pub y: u32,
 }
 
 impl PathPoint {
+    pub fn new(x: u32, y: u32) -> Self {
+        PathPoint { x, y }
+    }
+
     pub fn from_tuple(t: (u32, u32)) -> Self {
         PathPoint { x: t.0, y: t.1 }
     }
@@ -40,29 +42,35 @@ impl PartialOrd for Node {
     }
 }
 
-pub fn astar(grid: &[Vec<u8>]) -> Option<Vec<PathPoint>> {
-    let rows = grid.len() as u32;
-    if rows == 0 {
-        return None;
-    }
-    let cols = grid[0].len() as u32;
+/// Finds the shortest path between two free cells of the grid, moving in all 8 directions.
+/// Cells with a value of 1 are obstacles.
+pub fn astar(grid: &[Vec<u8>], start: (u32, u32), end: (u32, u32)) -> Option<Vec<PathPoint>> {
+    search(grid, None, start, end)
+}
 
-    let mut start = None;
-    let mut end = None;
+/// Same as `astar`, but moving onto a cell costs the value of that cell in `costs`
+/// instead of 1. This makes the search prefer cheap terrain (e.g. roads) over
+/// expensive terrain (e.g. swamps) without treating the latter as an obstacle.
+/// Costs of 0 are treated as 1.
+pub fn weighted_astar(
+    grid: &[Vec<u8>],
+    costs: &[Vec<u8>],
+    start: (u32, u32),
+    end: (u32, u32),
+) -> Option<Vec<PathPoint>> {
+    search(grid, Some(costs), start, end)
+}
 
-    for y in 0..rows {
-        for x in 0..cols {
-            match grid[y as usize][x as usize] {
-                2 => start = Some((x, y)),
-                3 => end = Some((x, y)),
-                _ => {}
-            }
-        }
+fn search(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    (start_x, start_y): (u32, u32),
+    (end_x, end_y): (u32, u32),
+) -> Option<Vec<PathPoint>> {
+    if grid.is_empty() {
+        return None;
     }
 
-    let (start_x, start_y) = start?;
-    let (end_x, end_y) = end?;
-
     let mut open_set = BinaryHeap::new();
     let mut came_from = HashMap::new();
     let mut g_score = HashMap::new();
@@ -85,7 +93,8 @@ pub fn astar(grid: &[Vec<u8>]) -> Option<Vec<PathPoint>> {
         let neighbors = get_neighbors(current.x, current.y, grid);
 
         for (nx, ny) in neighbors {
-            let tentative_g_score = g_score.get(&(current.x, current.y)).unwrap_or(&u32::MAX) + 1;
+            let tentative_g_score = g_score.get(&(current.x, current.y)).unwrap_or(&u32::MAX)
+                + step_cost(costs, PathPoint { x: nx, y: ny });
 
             if tentative_g_score < *g_score.get(&(nx, ny)).unwrap_or(&u32::MAX) {
                 came_from.insert((nx, ny), (current.x, current.y));
@@ -108,7 +117,8 @@ fn heuristic(x1: u32, y1: u32, x2: u32, y2: u32) -> u32 {
     (x1.abs_diff(x2)) + (y1.abs_diff(y2))
 }
 
-fn get_neighbors(x: u32, y: u32, grid: &[Vec<u8>]) -> Vec<(u32, u32)> {
+/// All free cells around a cell, including diagonal ones
+pub fn get_neighbors(x: u32, y: u32, grid: &[Vec<u8>]) -> Vec<(u32, u32)> {
     let rows = grid.len() as u32;
     let cols = grid[0].len() as u32;
     let mut neighbors = Vec::new();
@@ -156,3 +166,104 @@ fn reconstruct_path(
     total_path.reverse();
     Some(total_path)
 }
+
+/// Finds every cell which can be reached from `start` with a total movement cost
+/// of at most `budget` (Dijkstra's algorithm). Uses the same neighbours and step
+/// costs as `astar`. Returns the cost to reach each of those cells.
+pub fn movement_range(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    start: (u32, u32),
+    budget: u32,
+) -> HashMap<(u32, u32), u32> {
+    let mut distances = HashMap::new();
+    let mut open_set = BinaryHeap::new();
+    distances.insert(start, 0);
+    open_set.push(Reverse((0, start)));
+
+    while let Some(Reverse((distance, (x, y)))) = open_set.pop() {
+        if distance > *distances.get(&(x, y)).unwrap_or(&u32::MAX) {
+            continue; // Already reached with a lower cost
+        }
+        for (nx, ny) in get_neighbors(x, y, grid) {
+            let next = distance + step_cost(costs, PathPoint { x: nx, y: ny });
+            if next <= budget && next < *distances.get(&(nx, ny)).unwrap_or(&u32::MAX) {
+                distances.insert((nx, ny), next);
+                open_set.push(Reverse((next, (nx, ny))));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Shortens a path by "string pulling": every run of points that can see each
+/// other in a straight line is replaced by that line. The lines are rasterized
+/// again, so the result is still a list of neighbouring points and never
+/// crosses an obstacle. If `costs` are given a line is only used if it is not
+/// more expensive than the part of the path it replaces.
+pub fn smooth_path(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    path: &[PathPoint],
+) -> Vec<PathPoint> {
+    if path.len() < 3 {
+        return path.to_vec();
+    }
+
+    // Cost of walking the path up to (and including) each point
+    let mut path_costs = vec![0; path.len()];
+    for i in 1..path.len() {
+        path_costs[i] = path_costs[i - 1] + step_cost(costs, path[i]);
+    }
+
+    let mut smoothed = vec![path[0]];
+    let mut anchor = 0;
+    while anchor < path.len() - 1 {
+        let mut next = anchor + 1;
+        while next + 1 < path.len() {
+            let line = line_between(path[anchor], path[next + 1]);
+            let line_cost: u32 = line.iter().skip(1).map(|p| step_cost(costs, *p)).sum();
+            let clear = line.iter().all(|p| grid[p.y as usize][p.x as usize] != 1);
+            if !clear || line_cost > path_costs[next + 1] - path_costs[anchor] {
+                break;
+            }
+            next += 1;
+        }
+        smoothed.extend(line_between(path[anchor], path[next]).into_iter().skip(1));
+        anchor = next;
+    }
+
+    smoothed
+}
+
+/// The cost of moving onto a point, 1 if there is no cost layer
+pub fn step_cost(costs: Option<&[Vec<u8>]>, point: PathPoint) -> u32 {
+    costs.map_or(1, |c| c[point.y as usize][point.x as usize].max(1) as u32)
+}
+
+/// All points on the straight line between two points (Bresenham's line algorithm)
+pub fn line_between(from: PathPoint, to: PathPoint) -> Vec<PathPoint> {
+    let (mut x, mut y) = (from.x as i64, from.y as i64);
+    let (end_x, end_y) = (to.x as i64, to.y as i64);
+    let dx = (end_x - x).abs();
+    let dy = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    let mut line = vec![from];
+    while x != end_x || y != end_y {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        line.push(PathPoint::from_tuple((x as u32, y as u32)));
+    }
+    line
+}
@@ -0,0 +1,230 @@
+use crate::structs::error::{Result, RpgMapError};
+use crate::structs::map::{Fog, MapType, Overlay, RevealShape};
+use crate::structs::path::PathPoint;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The version of the serialized states. Bump it whenever `MapState` or `TravelState` change.
+///
+/// JSON states of older versions are read with defaults for the fields they lack. Binary
+/// states are not self-describing, so they are read with the layout of their version
+/// (see `MapStateV1`) and converted.
+pub const STATE_VERSION: u32 = 2;
+
+/// Everything needed to restore a map, as stored by `Map.to_json` and `Map.to_binary`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MapState {
+    pub version: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Left out if the bytes are stored somewhere else
+    pub bytes: Option<Vec<u8>>,
+    /// Hash of the bytes (see `hash_bytes`) to check bytes passed in separately
+    pub bytes_hash: String,
+    pub grid_size: u32,
+    pub unlocked: Vec<(u32, u32)>,
+    pub special_points: Vec<(u32, u32)>,
+    pub obstacles: Vec<Vec<(u32, u32)>>,
+    pub map_type: MapType,
+    pub draw_obstacles: bool,
+    pub dots: Vec<(u32, u32, [u8; 4], u32)>,
+    pub overlays: Vec<Overlay>,
+    pub should_draw_with_grid: bool,
+    pub should_draw_extras: bool,
+    pub cost_map: Option<Vec<u8>>,
+    pub edge_buffer: u32,
+    pub obstacle_buffer: u32,
+    pub obstacle_buffers: BTreeMap<usize, u32>,
+    #[serde(default)]
+    pub fog_feather: u32,
+    #[serde(default)]
+    pub fog: Fog,
+    #[serde(default)]
+    pub explored_fog: Option<f32>,
+    #[serde(default)]
+    pub current: Option<(u32, u32)>,
+    #[serde(default)]
+    pub line_of_sight: bool,
+    #[serde(default)]
+    pub reveal_shapes: Vec<((u32, u32), RevealShape)>,
+    #[serde(default)]
+    pub regions: BTreeMap<String, Vec<(u32, u32)>>,
+    #[serde(default)]
+    pub unlocked_regions: BTreeSet<String>,
+    #[serde(default)]
+    pub decay: Option<u32>,
+    #[serde(default)]
+    pub clock: u64,
+    #[serde(default)]
+    pub last_seen: Vec<((u32, u32), u64)>,
+}
+
+impl MapState {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(serialize_error)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self> {
+        let mut state: MapState = serde_json::from_str(data).map_err(deserialize_error)?;
+        check_version(state.version)?;
+        // Before version 2 the current position could be left out, and was the last unlocked point
+        if state.version < 2 {
+            state.current = state.current.or(state.unlocked.last().copied());
+        }
+        Ok(state)
+    }
+
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).map_err(serialize_error)
+    }
+
+    pub fn from_binary(data: &[u8]) -> Result<Self> {
+        match binary_version(data)? {
+            1 => Ok(deserialize_binary::<MapStateV1>(data)?.into()),
+            _ => deserialize_binary(data),
+        }
+    }
+}
+
+/// Everything needed to restore a travel, used to pickle it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TravelState {
+    pub version: u32,
+    pub map: MapState,
+    pub computed_path: Vec<PathPoint>,
+    pub legs: Vec<(usize, usize)>,
+    pub snap_distance: Option<f32>,
+}
+
+impl TravelState {
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).map_err(serialize_error)
+    }
+
+    pub fn from_binary(data: &[u8]) -> Result<Self> {
+        match binary_version(data)? {
+            1 => Ok(deserialize_binary::<TravelStateV1>(data)?.into()),
+            _ => deserialize_binary(data),
+        }
+    }
+}
+
+/// `MapState` in version 1, before the fog and exploration settings were added
+#[derive(Deserialize)]
+struct MapStateV1 {
+    version: u32,
+    width: u32,
+    height: u32,
+    bytes: Option<Vec<u8>>,
+    bytes_hash: String,
+    grid_size: u32,
+    unlocked: Vec<(u32, u32)>,
+    special_points: Vec<(u32, u32)>,
+    obstacles: Vec<Vec<(u32, u32)>>,
+    map_type: MapType,
+    draw_obstacles: bool,
+    dots: Vec<(u32, u32, [u8; 4], u32)>,
+    overlays: Vec<Overlay>,
+    should_draw_with_grid: bool,
+    should_draw_extras: bool,
+    cost_map: Option<Vec<u8>>,
+    edge_buffer: u32,
+    obstacle_buffer: u32,
+    obstacle_buffers: BTreeMap<usize, u32>,
+}
+
+impl From<MapStateV1> for MapState {
+    fn from(state: MapStateV1) -> Self {
+        // The current position was always the last unlocked point
+        let current = state.unlocked.last().copied();
+        MapState {
+            version: state.version,
+            width: state.width,
+            height: state.height,
+            bytes: state.bytes,
+            bytes_hash: state.bytes_hash,
+            grid_size: state.grid_size,
+            unlocked: state.unlocked,
+            special_points: state.special_points,
+            obstacles: state.obstacles,
+            map_type: state.map_type,
+            draw_obstacles: state.draw_obstacles,
+            dots: state.dots,
+            overlays: state.overlays,
+            should_draw_with_grid: state.should_draw_with_grid,
+            should_draw_extras: state.should_draw_extras,
+            cost_map: state.cost_map,
+            edge_buffer: state.edge_buffer,
+            obstacle_buffer: state.obstacle_buffer,
+            obstacle_buffers: state.obstacle_buffers,
+            fog_feather: 0,
+            fog: Fog::default(),
+            explored_fog: None,
+            current,
+            line_of_sight: false,
+            reveal_shapes: vec![],
+            regions: BTreeMap::new(),
+            unlocked_regions: BTreeSet::new(),
+            decay: None,
+            clock: 0,
+            last_seen: vec![],
+        }
+    }
+}
+
+/// `TravelState` in version 1, which contains a `MapStateV1`
+#[derive(Deserialize)]
+struct TravelStateV1 {
+    version: u32,
+    map: MapStateV1,
+    computed_path: Vec<PathPoint>,
+    legs: Vec<(usize, usize)>,
+    snap_distance: Option<f32>,
+}
+
+impl From<TravelStateV1> for TravelState {
+    fn from(state: TravelStateV1) -> Self {
+        TravelState {
+            version: state.version,
+            map: state.map.into(),
+            computed_path: state.computed_path,
+            legs: state.legs,
+            snap_distance: state.snap_distance,
+        }
+    }
+}
+
+/// Reads the version a binary state starts with, and checks that it can be read
+fn binary_version(data: &[u8]) -> Result<u32> {
+    let version = deserialize_binary(data)?;
+    check_version(version)?;
+    Ok(version)
+}
+
+fn deserialize_binary<T: serde::de::DeserializeOwned>(data: &[u8]) -> Result<T> {
+    bincode::deserialize(data).map_err(deserialize_error)
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version > STATE_VERSION {
+        return Err(RpgMapError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
+
+fn serialize_error(error: impl std::fmt::Display) -> RpgMapError {
+    RpgMapError::Serialize(error.to_string())
+}
+
+fn deserialize_error(error: impl std::fmt::Display) -> RpgMapError {
+    RpgMapError::Deserialize(error.to_string())
+}
+
+/// A hash of image bytes which is stable across platforms and versions (64 bit FNV-1a),
+/// unlike the hashers of the standard library
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
@@ -1,15 +1,23 @@
-use crate::structs::path::{astar, PathPoint};
+use crate::structs::path::{astar, movement_range, smooth_path, weighted_astar, PathPoint};
 use core::panic;
-use pyo3::prelude::*;
+use std::collections::HashMap;
 use std::vec;
 use workaround::stubgen;
 
+use crate::structs::error::{Result, RpgMapError};
 use crate::structs::map::Map;
-use geo::{Contains, Coord, LineString, Point, Polygon};
+use crate::structs::navigation::NavigationGrid;
+use crate::structs::state::{TravelState, STATE_VERSION};
 
 /// A class representing a travel from one point to another on a map.
 /// This class contains the shortest path from point A to point B on the map.
-/// It uses the A* algorithm to find the path.
+/// It uses the A* algorithm to find the path. If the map has a cost layer
+/// (see :func:`Map.with_cost_map`) the path is the cheapest instead of the shortest one.
+///
+/// The grid used for pathfinding is built once per map and reused by every
+/// following travel on it (or a copy of it) until the map changes.
+/// The path is computed without holding the Python GIL, so several travels
+/// can be computed in parallel threads.
 ///
 /// Parameters
 /// ----------
@@ -19,184 +27,243 @@ use geo::{Contains, Coord, LineString, Point, Polygon};
 ///    The current location of the traveler. Given as a tuple of (x, y) coordinates.
 /// destination : tuple[int, int]
 ///    The destination of the traveler. Given as a tuple of (x, y) coordinates.
+/// smooth : bool
+///    If true, the path is straightened into natural looking lines at any angle instead of
+///    only horizontal, vertical and diagonal steps. It still never crosses an obstacle.
+/// snap_destination : bool
+///    If true, a destination which is an obstacle or cannot be reached is moved to the
+///    closest point which can be reached instead of raising an error.
+/// hierarchical : bool
+///    If true, the path is found on a coarse graph of the map first and only refined
+///    where the path goes (HPA*). This is much faster and uses less memory on very
+///    large maps, but the path can be slightly longer than the shortest one. The graph is
+///    built on first use and cached like the pathfinding grid. If there is no path or the
+///    destination has to be snapped, the whole map is still checked like without it.
 ///     
 /// Attributes
 /// ---------
 /// computed_path : list[PathPoint]
 ///    The computed path from the current location to the destination.
+/// legs : list[tuple[int, int]]
+///    The start and end index in `computed_path` of every leg of the travel.
+///    A travel from a current location to a destination has a single leg.
+/// snap_distance : Optional[float]
+///    How far in pixels the destination was moved to reach it, or None if it was not moved.
+///
+/// Raises
+/// ------
+/// OutOfBoundsError
+///    If the current location or destination is outside of the map.
+/// BlockedEndpointError
+///    If the current location or destination is an obstacle.
+/// NoPathError
+///    If the destination cannot be reached from the current location.
 #[stubgen]
-#[pyclass]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map"))]
 #[derive(Clone)]
 pub struct Travel {
-    pub map: Map,
-    #[pyo3(get)]
-    pub computed_path: Vec<PathPoint>,
+    map: Map,
+    computed_path: Vec<PathPoint>,
+    legs: Vec<(usize, usize)>,
+    snap_distance: Option<f32>,
 }
 
-/// Give all 1s a X px "buffer" of 1s around them
-/// For efficiency reasons it will only do this if a 0
-/// is within a 1px radius of the 1
-fn buffer_edges(reduced: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let buffer_size = 5;
-    let mut new = reduced.clone();
-    // We do not want to mutate the original in the loop
-
-    for y in 0..reduced.len() {
-        for x in 0..reduced[y].len() {
-            if reduced[y][x] == 0 {
-                continue;
-            }
+/// All points which can be reached from an origin within a movement budget,
+/// as returned by :func:`Travel.movement_range`.
+///
+/// Attributes
+/// ---------
+/// origin : tuple[int, int]
+///    The point the movement starts from.
+/// budget : int
+///    The maximum movement cost.
+/// distances : dict[tuple[int, int], int]
+///    The movement cost to reach every reachable point, including the origin.
+#[stubgen]
+#[cfg_attr(feature = "python", pyo3::pyclass(module = "rpg_map"))]
+#[derive(Clone)]
+pub struct MovementRange {
+    pub origin: (u32, u32),
+    pub budget: u32,
+    pub distances: HashMap<(u32, u32), u32>,
+}
 
-            let mut buffer = false;
-            for i in -1..2 {
-                for j in -1..2 {
-                    if y as i32 + i < 0
-                        || y as i32 + i >= reduced.len() as i32
-                        || x as i32 + j < 0
-                        || x as i32 + j >= reduced[y].len() as i32
-                    {
-                        continue;
-                    }
-
-                    if reduced[(y as i32 + i) as usize][(x as i32 + j) as usize] == 0 {
-                        buffer = true;
-                        break;
-                    }
-                }
-            }
+/// How the path of a :class:`Travel` is found. Every option is off by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TravelOptions {
+    /// Straighten the path into lines at any angle
+    pub smooth: bool,
+    /// Move a blocked or unreachable destination to the closest reachable point
+    pub snap_destination: bool,
+    /// Find the path on a coarse graph of the map first (HPA*)
+    pub hierarchical: bool,
+}
 
-            if buffer {
-                for i in -buffer_size..(buffer_size + 1) {
-                    for j in -buffer_size..(buffer_size + 1) {
-                        if y as i32 + i < 0
-                            || y as i32 + i >= reduced.len() as i32
-                            || x as i32 + j < 0
-                            || x as i32 + j >= reduced[y].len() as i32
-                        {
-                            continue;
-                        }
-
-                        new[(y as i32 + i) as usize][(x as i32 + j) as usize] = 1;
-                    }
-                }
-            }
-        }
-    }
+/// Finds the path of a single leg of a travel.
+/// If the destination was snapped, the distance it was moved by is returned as well.
+fn find_leg(
+    map: &Map,
+    navigation: &NavigationGrid,
+    current_location: (u32, u32),
+    mut destination: (u32, u32),
+    options: TravelOptions,
+) -> Result<(Vec<PathPoint>, Option<f32>)> {
+    let grid = &navigation.grid;
+    let costs = navigation.costs.as_deref();
 
-    new
-}
+    check_bounds(map, current_location)?;
+    check_bounds(map, destination)?;
 
-/// Converts the image to a grid where 0 is a free space and 1 is an obstacle
-pub fn image_to_grid(map: &mut Map) -> Vec<Vec<u8>> {
-    let mut grid = vec![vec![0; (map.width) as usize]; (map.height) as usize];
-    let binding: Vec<u8> = map.get_bits();
-    for (i, byte) in binding.chunks_exact(4).enumerate() {
-        let x = i % map.width as usize;
-        let y = i / map.width as usize;
-        let alpha = byte[3]; // Alpha channel
-        if alpha == 0 {
-            grid[y][x] = 1; // Transparent pixels -> Obstacle
+    let requested = destination;
+    if options.snap_destination && !navigation.is_reachable(current_location, destination) {
+        if let Some(closest) = navigation.closest_reachable(current_location, destination) {
+            destination = closest;
         }
     }
 
-    // Step 2: Process polygon obstacles
-    let mut polygons: Vec<Polygon> = vec![];
-    for obstacle in &map.obstacles {
-        if obstacle.len() < 3 {
-            continue; // Skip invalid polygons
+    // If current location or destination is an obstacle, return an error
+    for point in [current_location, destination] {
+        if grid[point.1 as usize][point.0 as usize] == 1 {
+            return Err(RpgMapError::BlockedEndpoint { point });
         }
-        let exterior = obstacle
-            .iter()
-            .map(|&coords| Coord {
-                x: coords.0 as f64,
-                y: coords.1 as f64,
+    }
+
+    // A leg which does not move, e.g. between identical waypoints, is just its one point
+    let path = if current_location == destination {
+        Some(vec![PathPoint::new(destination.0, destination.1)])
+    } else if options.hierarchical {
+        // Labelling the regions takes as much memory as the grid, so they are only used to
+        // tell if the coarse graph missed a way when it finds none
+        navigation
+            .hierarchy()
+            .find_path(grid, costs, current_location, destination)
+            .or_else(|| {
+                navigation
+                    .is_reachable(current_location, destination)
+                    .then(|| shortest_path(grid, costs, current_location, destination))
+                    .flatten()
             })
-            .collect::<Vec<Coord>>();
+    } else if !navigation.is_reachable(current_location, destination) {
+        // Points in different regions can never be connected, so there is no need to search
+        None
+    } else {
+        shortest_path(grid, costs, current_location, destination)
+    };
 
-        let polygon = Polygon::new(LineString::from(exterior), vec![]);
-        polygons.push(polygon);
+    let snap_distance = (destination != requested).then(|| {
+        let dx = destination.0 as f32 - requested.0 as f32;
+        let dy = destination.1 as f32 - requested.1 as f32;
+        (dx * dx + dy * dy).sqrt()
+    });
+    match path {
+        Some(path) if options.smooth => Ok((smooth_path(grid, costs, &path), snap_distance)),
+        Some(path) => Ok((path, snap_distance)),
+        None => Err(RpgMapError::NoPath {
+            start: current_location,
+            destination,
+        }),
     }
+}
 
-    for y in 0..map.height {
-        for x in 0..map.width {
-            for polygon in &polygons {
-                if polygon.contains(&Point::new(x as f64, y as f64)) {
-                    grid[y as usize][x as usize] = 1; // Mark obstacle
-                }
-            }
-        }
+/// Returns an error if the point is not on the map
+fn check_bounds(map: &Map, point: (u32, u32)) -> Result<()> {
+    if point.0 >= map.width() || point.1 >= map.height() {
+        return Err(RpgMapError::OutOfBounds {
+            point,
+            width: map.width(),
+            height: map.height(),
+        });
     }
+    Ok(())
+}
 
-    // Step 3: Buffer edges of obstacles
-    grid = buffer_edges(grid);
-
-    grid
+/// Runs A* between two free points of the grid
+fn shortest_path(
+    grid: &[Vec<u8>],
+    costs: Option<&[Vec<u8>]>,
+    current_location: (u32, u32),
+    destination: (u32, u32),
+) -> Option<Vec<PathPoint>> {
+    match costs {
+        Some(costs) => weighted_astar(grid, costs, current_location, destination),
+        None => astar(grid, current_location, destination),
+    }
 }
 
-#[stubgen]
-#[pymethods]
 impl Travel {
-    #[new]
+    /// Finds the path between two points, see :class:`Travel`
     pub fn new(
-        mut map: Map,
+        map: Map,
         current_location: (u32, u32),
         destination: (u32, u32),
-    ) -> PyResult<Travel> {
-        // draw obstacles on the map
-        let mut grid = image_to_grid(&mut map);
-
-        // If current location or destination is out of bounds, return an error
-        if current_location.0 >= map.width
-            || current_location.1 >= map.height
-            || destination.0 >= map.width
-            || destination.1 >= map.height
-        {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                "Current location or destination is out of bounds",
-            ));
-        }
-        // If current location or destination is an obstacle, return an error
-        if grid[current_location.1 as usize][current_location.0 as usize] == 1
-            || grid[destination.1 as usize][destination.0 as usize] == 1
-        {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                "Current location or destination is an obstacle",
-            ));
+        options: TravelOptions,
+    ) -> Result<Travel> {
+        Travel::from_waypoints(map, vec![current_location, destination], options)
+    }
+
+    /// Finds the path along all waypoints, see :func:`Travel.from_waypoints`
+    pub fn from_waypoints(
+        map: Map,
+        waypoints: Vec<(u32, u32)>,
+        options: TravelOptions,
+    ) -> Result<Travel> {
+        if waypoints.len() < 2 {
+            return Err(RpgMapError::invalid("At least two waypoints are required"));
         }
 
-        // put in start and end
-        grid[current_location.1 as usize][current_location.0 as usize] = 2;
-        grid[destination.1 as usize][destination.0 as usize] = 3;
-
-        match astar(&grid) {
-            Some(path) => Ok(Travel {
-                map,
-                computed_path: path,
-            }),
-            None => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                "No path found",
-            )),
+        let navigation = map.navigation_grid();
+
+        let mut computed_path: Vec<PathPoint> = vec![];
+        let mut legs = vec![];
+        let mut snap_distance = None;
+        for (i, leg) in waypoints.windows(2).enumerate() {
+            let is_last = i == waypoints.len() - 2;
+            // Only the destination of the whole travel is snapped
+            let options = TravelOptions {
+                snap_destination: options.snap_destination && is_last,
+                ..options
+            };
+            let (path, snapped) = find_leg(&map, &navigation, leg[0], leg[1], options)?;
+            snap_distance = snapped;
+            let start = computed_path.len().saturating_sub(1);
+            // The first point of a leg is the last point of the previous one
+            let skip = if computed_path.is_empty() { 0 } else { 1 };
+            computed_path.extend(path.into_iter().skip(skip));
+            legs.push((start, computed_path.len() - 1));
         }
+
+        Ok(Travel {
+            map,
+            computed_path,
+            legs,
+            snap_distance,
+        })
+    }
+
+    /// The map the travel is on
+    pub fn map(&self) -> &Map {
+        &self.map
     }
 
-    /// Displays the map in a black and white view where white are the
-    /// obstacles and black are the free spaces. This is to debug if
-    /// a fault is with the pathfinding algorithm or the map reduction
-    /// algorithm.
-    ///
-    /// Parameters
-    /// ---------
-    /// map : Map
-    ///   The map to display the black and white view of.
-    ///
-    /// Returns
-    /// -------
-    /// list[int]
-    ///   A list of bytes representing the black and white view of the map.
-    #[staticmethod]
-    pub fn dbg_map(mut map: Map) -> Vec<u8> {
-        let grid = image_to_grid(&mut map);
-        let mut long_map = vec![0; map.width as usize * map.height as usize * 4];
+    /// The path from the current location to the destination, see :class:`Travel`
+    pub fn computed_path(&self) -> &[PathPoint] {
+        &self.computed_path
+    }
+
+    /// The start and end index in the path of every leg, see :class:`Travel`
+    pub fn legs(&self) -> &[(usize, usize)] {
+        &self.legs
+    }
+
+    /// How far the destination was moved to reach it, see :class:`Travel`
+    pub fn snap_distance(&self) -> Option<f32> {
+        self.snap_distance
+    }
+
+    /// The navigation grid of the map as an image, see :func:`Travel.dbg_map`
+    pub fn dbg_map(map: Map) -> Vec<u8> {
+        let grid = &map.navigation_grid().grid;
+        let mut long_map = vec![0; map.width() as usize * map.height() as usize * 4];
         for y in 0..grid.len() {
             for x in 0..grid[y].len() {
                 let byte = match grid[y][x] {
@@ -206,15 +273,86 @@ impl Travel {
                     3 => vec![255, 0, 0, 255],
                     _ => panic!("Invalid grid value"),
                 };
-                if y * map.width as usize + x + 4 >= long_map.len() {
+                if y * map.width() as usize + x + 4 >= long_map.len() {
                     println!("{byte:?}");
                     continue;
                 }
-                long_map
-                    [y * map.width as usize * 4 + x * 4..y * map.width as usize * 4 + x * 4 + 4]
+                long_map[y * map.width() as usize * 4 + x * 4
+                    ..y * map.width() as usize * 4 + x * 4 + 4]
                     .copy_from_slice(&byte);
             }
         }
         long_map
     }
+    /// Serializes the travel, including its map and path, see :func:`Travel.to_binary`
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        let state = TravelState {
+            version: STATE_VERSION,
+            map: self.map.state(true),
+            computed_path: self.computed_path.clone(),
+            legs: self.legs.clone(),
+            snap_distance: self.snap_distance,
+        };
+        state.to_binary()
+    }
+
+    /// Restores a travel without computing the path again, see :func:`Travel.from_binary`
+    pub fn from_binary(data: &[u8]) -> Result<Travel> {
+        let state = TravelState::from_binary(data)?;
+        let map = Map::from_state(state.map, None)?;
+        // Drawing the travel indexes the path by its legs and the map by its points
+        let path = &state.computed_path;
+        if path.is_empty()
+            || state
+                .legs
+                .iter()
+                .any(|&(start, end)| start > end || end >= path.len())
+        {
+            return Err(RpgMapError::invalid(
+                "The path of the travel is empty or does not match its legs",
+            ));
+        }
+        for point in path {
+            check_bounds(&map, (point.x, point.y))?;
+        }
+        Ok(Travel {
+            map,
+            computed_path: state.computed_path,
+            legs: state.legs,
+            snap_distance: state.snap_distance,
+        })
+    }
+
+    /// Checks if there is a path between two points, see :func:`Travel.is_reachable`
+    pub fn is_reachable(
+        map: &Map,
+        current_location: (u32, u32),
+        destination: (u32, u32),
+    ) -> Result<bool> {
+        check_bounds(map, current_location)?;
+        check_bounds(map, destination)?;
+        Ok(map
+            .navigation_grid()
+            .is_reachable(current_location, destination))
+    }
+
+    /// Finds the region a point is in, see :func:`Travel.region_of`
+    pub fn region_of(map: &Map, point: (u32, u32)) -> Result<Option<u32>> {
+        check_bounds(map, point)?;
+        Ok(map.navigation_grid().region_of(point))
+    }
+
+    /// Finds every point which can be reached within a budget, see :func:`Travel.movement_range`
+    pub fn movement_range(map: &Map, start: (u32, u32), budget: u32) -> Result<MovementRange> {
+        check_bounds(map, start)?;
+        let navigation = map.navigation_grid();
+        if navigation.grid[start.1 as usize][start.0 as usize] == 1 {
+            return Err(RpgMapError::BlockedEndpoint { point: start });
+        }
+        Ok(MovementRange {
+            origin: start,
+            budget,
+            distances: movement_range(&navigation.grid, navigation.costs.as_deref(), start, budget),
+        })
+    }
 }
@@ -0,0 +1,65 @@
+use crate::structs::map::Map;
+use crate::structs::navigation::{fill_polygon, transparent_threshold};
+
+/// Marks every pixel which blocks the view with 1. These are the pixels inside
+/// obstacles and the transparent pixels of the map, without any buffer.
+pub fn occluders(map: &Map) -> Vec<Vec<u8>> {
+    let mut grid = vec![vec![0; map.width() as usize]; map.height() as usize];
+    let threshold = transparent_threshold(map.map_type);
+    for (i, byte) in map.bytes().chunks_exact(4).enumerate() {
+        if byte[3] < threshold {
+            grid[i / map.width() as usize][i % map.width() as usize] = 1;
+        }
+    }
+    for obstacle in map
+        .obstacles()
+        .iter()
+        .filter(|obstacle| obstacle.len() >= 3)
+    {
+        fill_polygon(&mut grid, obstacle);
+    }
+    grid
+}
+
+/// Casts rays from `center` to every pixel on the border of the square of `radius` around it.
+/// Every ray stops at the first occluder it hits, which is visible itself, like the face of a wall.
+///
+/// Returns whether each pixel of the square is visible, row by row. The square
+/// has a side length of `2 * radius + 1` and is centered on `center`.
+pub fn visible_area(occluders: &[Vec<u8>], center: (i32, i32), radius: i32) -> Vec<bool> {
+    let side = (2 * radius + 1) as usize;
+    let mut visible = vec![false; side * side];
+    let height = occluders.len() as i32;
+    let width = occluders.first().map_or(0, |row| row.len()) as i32;
+    let (cx, cy) = center;
+    visible[radius as usize * side + radius as usize] = true;
+
+    let border =
+        (-radius..=radius).flat_map(|i| [(i, -radius), (i, radius), (-radius, i), (radius, i)]);
+    for (tx, ty) in border {
+        // Bresenham's line algorithm from the center to the border
+        let (dx, dy) = (tx.abs(), -ty.abs());
+        let (sx, sy) = (tx.signum(), ty.signum());
+        let (mut x, mut y, mut error) = (0, 0, dx + dy);
+        while (x, y) != (tx, ty) {
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+            let (map_x, map_y) = (cx + x, cy + y);
+            if map_x < 0 || map_x >= width || map_y < 0 || map_y >= height {
+                break;
+            }
+            visible[(y + radius) as usize * side + (x + radius) as usize] = true;
+            if occluders[map_y as usize][map_x as usize] != 0 {
+                break;
+            }
+        }
+    }
+    visible
+}
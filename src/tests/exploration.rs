@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod exploration_tests {
+    use crate::structs::exploration::ExplorationGrid;
+    use crate::structs::map::Map;
+    use crate::structs::map::MapType;
+
+    /// The grid points as they were listed before they were stored in a bitset
+    fn grid_points(width: u32, height: u32, grid_size: u32) -> Vec<(u32, u32)> {
+        let mut grid_points = Vec::new();
+        for y in (0..height).step_by(grid_size as usize) {
+            for x in (0..width).step_by(grid_size as usize) {
+                grid_points.push((x, y));
+            }
+        }
+        for x in (0..width).step_by(grid_size as usize) {
+            grid_points.push((x, height - 1));
+        }
+        for y in (0..height).step_by(grid_size as usize) {
+            grid_points.push((width - 1, y));
+        }
+        grid_points
+    }
+
+    /// Finds the closest grid point by scanning all of them
+    fn closest_by_scan(grid_points: &[(u32, u32)], point: (u32, u32)) -> (u32, u32) {
+        let mut min_dist = u32::MAX;
+        let mut closest_point = (0, 0);
+        for p in grid_points {
+            let dist = p.0.abs_diff(point.0) + p.1.abs_diff(point.1);
+            if dist < min_dist {
+                min_dist = dist;
+                closest_point = *p;
+            }
+        }
+        closest_point
+    }
+
+    #[test]
+    fn test_closest_matches_scan() {
+        for (width, height, grid_size) in [
+            (31, 21, 10),
+            (30, 20, 10),
+            (41, 17, 10),
+            (7, 9, 3),
+            (1, 5, 2),
+            (25, 25, 7),
+        ] {
+            let grid = ExplorationGrid::new(width, height, grid_size);
+            let grid_points = grid_points(width, height, grid_size);
+            for y in 0..height + 3 {
+                for x in 0..width + 3 {
+                    assert_eq!(
+                        grid.closest((x, y)),
+                        closest_by_scan(&grid_points, (x, y)),
+                        "({x}, {y}) on a {width}x{height} map with a grid size of {grid_size}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unlock_and_lock() {
+        let mut grid = ExplorationGrid::new(35, 35, 10);
+        assert!(grid.insert((10, 20)));
+        assert!(!grid.insert((10, 20)));
+        assert!(grid.contains((10, 20)));
+        assert!(!grid.contains((20, 10)));
+        // Only grid points can be unlocked
+        assert!(!grid.insert((11, 20)));
+        assert!(!grid.insert((34, 34)));
+        assert!(grid.insert((34, 30)));
+        assert!(grid.remove((10, 20)));
+        assert!(!grid.remove((10, 20)));
+        assert!(!grid.contains((10, 20)));
+    }
+
+    #[test]
+    fn test_unlocked_cells() {
+        let mut grid = ExplorationGrid::new(35, 35, 10);
+        for point in [
+            (0, 0),
+            (10, 0),
+            (0, 10),
+            (10, 10),
+            (20, 10),
+            (30, 10),
+            (30, 0),
+            (34, 0),
+            (34, 10),
+        ] {
+            grid.insert(point);
+        }
+        assert_eq!(
+            grid.unlocked_cells().collect::<Vec<_>>(),
+            vec![((0, 0), (10, 10)), ((30, 0), (34, 10))]
+        );
+    }
+
+    #[test]
+    fn test_many_unlocked_points() {
+        let mut map = Map::new(
+            [100, 100, 100, 255].repeat(1000 * 1000),
+            1000,
+            1000,
+            10,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        for y in (0..1000).step_by(10) {
+            for x in (0..1000).step_by(10) {
+                assert!(map.unlock_point_from_coordinates(x, y));
+            }
+        }
+        assert_eq!(map.unlocked().len(), 10_000);
+        assert!(!map.unlock_point_from_coordinates(504, 496));
+        // The last row and column of the map were not unlocked
+        let image = map.masked_image();
+        assert!(image
+            .chunks_exact(4 * 1000)
+            .take(990)
+            .all(|row| row[..990 * 4].chunks_exact(4).all(|pixel| pixel[0] == 100)));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::structs::hierarchy::Hierarchy;
+use crate::structs::path::{astar, PathPoint};
+
+fn assert_walkable(grid: &[Vec<u8>], path: &[PathPoint]) {
+    for pair in path.windows(2) {
+        assert!(pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1);
+        assert_ne!(grid[pair[1].y as usize][pair[1].x as usize], 1);
+    }
+}
+
+#[test]
+fn test_hierarchical_path() {
+    // A wall with a single gap at the bottom, which forces the path through several clusters
+    let mut grid = vec![vec![0; 12]; 12];
+    for row in grid.iter_mut().take(11) {
+        row[5] = 1;
+    }
+    let hierarchy = Hierarchy::new(&grid, None, 4);
+
+    let path = hierarchy.find_path(&grid, None, (0, 0), (11, 0)).unwrap();
+    assert_eq!(path.first(), Some(&PathPoint::from_tuple((0, 0))));
+    assert_eq!(path.last(), Some(&PathPoint::from_tuple((11, 0))));
+    assert!(path.contains(&PathPoint::from_tuple((5, 11))));
+    assert_walkable(&grid, &path);
+
+    // Not much longer than the shortest path
+    let shortest = astar(&grid, (0, 0), (11, 0)).unwrap();
+    assert!(path.len() <= shortest.len() + shortest.len() / 4);
+
+    // Within a single cluster
+    let path = hierarchy.find_path(&grid, None, (0, 0), (3, 2)).unwrap();
+    assert_eq!(path.len(), 4);
+}
+
+#[test]
+fn test_hierarchical_path_crossing_diagonally() {
+    // The only way between the two clusters is the diagonal step from (3, 1) to (4, 2)
+    let mut grid = vec![vec![0; 8]; 4];
+    for (y, row) in grid.iter_mut().enumerate() {
+        row[3] = (y != 1) as u8;
+        row[4] = (y != 2) as u8;
+    }
+    let hierarchy = Hierarchy::new(&grid, None, 4);
+
+    let path = hierarchy.find_path(&grid, None, (0, 0), (7, 3)).unwrap();
+    assert!(path.contains(&PathPoint::from_tuple((3, 1))));
+    assert!(path.contains(&PathPoint::from_tuple((4, 2))));
+    assert_walkable(&grid, &path);
+
+    // Blocking the diagonal disconnects the clusters
+    grid[1][3] = 1;
+    let hierarchy = Hierarchy::new(&grid, None, 4);
+    assert_eq!(hierarchy.find_path(&grid, None, (0, 0), (7, 3)), None);
+}
+
+#[test]
+fn test_hierarchical_path_crossing_corner() {
+    // Only the top left and bottom right cluster are free, and they only share a corner
+    let grid = (0..8)
+        .map(|y| {
+            (0..8)
+                .map(|x| ((x < 4) != (y < 4)) as u8)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let hierarchy = Hierarchy::new(&grid, None, 4);
+
+    let path = hierarchy.find_path(&grid, None, (0, 0), (7, 7)).unwrap();
+    assert!(path.contains(&PathPoint::from_tuple((3, 3))));
+    assert!(path.contains(&PathPoint::from_tuple((4, 4))));
+    assert_walkable(&grid, &path);
+
+    // The same for the other two clusters
+    let flipped = grid
+        .iter()
+        .map(|row| row.iter().map(|cell| 1 - cell).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let hierarchy = Hierarchy::new(&flipped, None, 4);
+    let path = hierarchy.find_path(&flipped, None, (7, 0), (0, 7)).unwrap();
+    assert!(path.contains(&PathPoint::from_tuple((4, 3))));
+    assert!(path.contains(&PathPoint::from_tuple((3, 4))));
+    assert_walkable(&flipped, &path);
+}
@@ -1,22 +1,23 @@
 use super::utils::{compare_images, get_image_bits};
-use pyo3::prelude::{Py, PyErr, PyRefMut, Python};
 
 #[cfg(test)]
 mod map_tests {
     use super::*;
+    use crate::structs::error::RpgMapError;
     use crate::structs::map::Map;
     use crate::structs::map::MapType;
     use crate::structs::map::PathDisplayType;
     use crate::structs::map::PathProgressDisplayType;
     use crate::structs::map::PathStyle;
-    use crate::structs::travel::Travel;
+    use crate::structs::map::RevealShape;
+    use crate::structs::travel::{Travel, TravelOptions};
 
     #[test]
     fn test_full_map_creation() {
         let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
         let (background, _, _) = get_image_bits("test_assets", "background.png");
         let (expected, _, _) = get_image_bits("test_results", "full.png");
-        let map = Map::new(
+        let mut map = Map::new(
             image.clone(),
             image_width,
             image_height,
@@ -25,32 +26,32 @@ mod map_tests {
             vec![],
             vec![],
             vec![],
-        );
-        let travel = Travel::new(map.clone(), (198, 390), (330, 512)).unwrap();
-        Python::with_gil(|py| -> Result<(), PyErr> {
-            let n: Py<Map> = Py::new(py, map).expect("Failed to create Py<Map>");
-            let guard: PyRefMut<'_, Map> = n.bind(py).borrow_mut();
-
-            let result = Map::draw_background(
-                Map::with_dot(guard, 198, 390, [255, 0, 0, 255], 5)
-                    .draw_path(
-                        travel,
-                        1.0,
-                        2,
-                        PathStyle::DottedWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
-                        PathDisplayType::BelowMask,
-                        PathProgressDisplayType::Travelled,
-                    )
-                    .expect("Failed to draw path"),
-                background,
-            )
-            .expect("Failed to generate bits");
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
 
-            compare_images(&result, &expected, &image, image_width, image_height);
+        let result = Map::draw_background(
+            map.with_dot(198, 390, [255, 0, 0, 255], 5)
+                .draw_path(
+                    travel,
+                    1.0,
+                    2,
+                    PathStyle::DottedWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
+                    PathDisplayType::BelowMask,
+                    PathProgressDisplayType::Travelled,
+                )
+                .expect("Failed to draw path"),
+            background,
+        )
+        .expect("Failed to generate bits");
 
-            Ok(())
-        })
-        .expect("Failed to execute Python code");
+        compare_images(&result, &expected, &image, image_width, image_height);
     }
 
     #[test]
@@ -66,8 +67,15 @@ mod map_tests {
             vec![],
             vec![],
             vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
-        );
-        let travel = Travel::new(map.clone(), (198, 390), (172, 223)).unwrap();
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (172, 223),
+            TravelOptions::default(),
+        )
+        .unwrap();
 
         let result = map
             .draw_path(
@@ -96,8 +104,15 @@ mod map_tests {
             vec![],
             vec![],
             vec![],
-        );
-        let travel = Travel::new(map.clone(), (198, 390), (330, 512)).unwrap();
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
 
         let result = map
             .draw_path(
@@ -121,8 +136,555 @@ mod map_tests {
             Ok(_) => panic!("Expected an error, but got a valid image"),
             Err(e) => assert_eq!(
                 e.to_string(),
-                "ValueError: Background image must have the same size as the map"
+                "Background image must have the same size as the map (1412000 bytes), but has 4096 bytes"
+            ),
+        }
+    }
+
+    #[test]
+    fn test_invalid_map_arguments() {
+        let new = |bytes: Vec<u8>, width, height, grid_size, obstacles| {
+            Map::new(
+                bytes,
+                width,
+                height,
+                grid_size,
+                MapType::Full,
+                vec![],
+                vec![],
+                obstacles,
+            )
+        };
+        let error = |result: crate::structs::error::Result<Map>| match result {
+            Ok(_) => panic!("Expected an error, but got a valid map"),
+            Err(e) => e.to_string(),
+        };
+
+        assert!(new(vec![0; 16], 2, 2, 1, vec![]).is_ok());
+        assert_eq!(
+            error(new(vec![], 0, 2, 1, vec![])),
+            "The map must be at least 1x1 pixels, got 0x2"
+        );
+        assert_eq!(
+            error(new(vec![0; 15], 2, 2, 1, vec![])),
+            "Image must have the same size as the map (16 bytes), but has 15 bytes"
+        );
+        assert_eq!(
+            error(new(vec![0; 16], 2, 2, 0, vec![])),
+            "Grid size must be at least 1"
+        );
+        match new(vec![0; 16], 2, 2, 1, vec![vec![(0, 0), (1, 0), (2, 1)]]) {
+            Ok(_) => panic!("Expected an error, but got a valid map"),
+            Err(e) => assert!(matches!(
+                e,
+                RpgMapError::OutOfBounds {
+                    point: (2, 1),
+                    width: 2,
+                    height: 2
+                }
+            )),
+        }
+        assert_eq!(
+            error(Map::new(
+                vec![0; 16],
+                2,
+                2,
+                1,
+                MapType::Limited,
+                vec![(0, 5)],
+                vec![],
+                vec![]
+            )),
+            "Point (0, 5) is out of bounds of the 2x2 map"
+        );
+    }
+
+    #[test]
+    fn test_invalid_line_width() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
+
+        for (line_width, path_type, min) in [
+            (0, PathStyle::Solid([255, 0, 0, 255]), 1),
+            (21, PathStyle::Solid([255, 0, 0, 255]), 1),
+            (
+                1,
+                PathStyle::SolidWithOutline([255, 0, 0, 255], [0, 0, 0, 255]),
+                2,
             ),
+        ] {
+            match map.draw_path(
+                travel.clone(),
+                1.0,
+                line_width,
+                path_type,
+                PathDisplayType::BelowMask,
+                PathProgressDisplayType::Travelled,
+            ) {
+                Ok(_) => panic!("Expected an error, but got a valid image"),
+                Err(e) => {
+                    assert!(matches!(
+                        e,
+                        RpgMapError::InvalidStyle { line_width: width, min: m, max: 20 }
+                            if width == line_width && m == min
+                    ));
+                    assert_eq!(
+                        e.to_string(),
+                        format!("Line width {line_width} must be between {min} and 20 for this path style")
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fog_feather() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![(200, 400)],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        map.with_fog_feather(10);
+        let full = map.full_image();
+        let masked = map.masked_image();
+        let pixel = |image: &[u8], x: u32, y: u32| {
+            let index = ((y * image_width + x) * 4) as usize;
+            image[index..index + 4].to_vec()
+        };
+
+        // The unlocked circle has a radius of 25px, of which the outer 10px fade into the fog
+        assert_eq!(pixel(&masked, 200, 400), pixel(&full, 200, 400));
+        assert_eq!(pixel(&masked, 260, 400), vec![0, 0, 0, 255]);
+        let (edge, revealed) = (pixel(&masked, 220, 400), pixel(&full, 220, 400));
+        for channel in 0..3 {
+            let expected = (revealed[channel] as f32 * (1.0 - 128.0 / 255.0)).round() as u8;
+            assert_eq!(edge[channel], expected);
+        }
+
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false).fog_feather, 10);
+    }
+
+    #[test]
+    fn test_fog_styles() {
+        // A fully hidden 4x4 map, so every pixel is covered by the fog
+        let mut map = Map::new(
+            [100, 150, 200, 255].repeat(16),
+            4,
+            4,
+            2,
+            MapType::Hidden,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(map.masked_image(), [0, 0, 0, 255].repeat(16));
+
+        map.with_fog_color([0, 0, 0, 128]);
+        assert_eq!(&map.masked_image()[..4], &[50, 75, 100, 255]);
+
+        // A 2x1 texture repeats every two pixels
+        map.with_fog_texture(vec![255, 0, 0, 255, 0, 255, 0, 255], 2, 1)
+            .unwrap();
+        let image = map.masked_image();
+        assert_eq!(&image[..4], &[255, 0, 0, 255]);
+        assert_eq!(&image[4..8], &[0, 255, 0, 255]);
+        assert_eq!(&image[8..12], &[255, 0, 0, 255]);
+        assert_eq!(&image[16..20], &[255, 0, 0, 255]);
+
+        map.with_fog_shade(0.5, 0.0).unwrap();
+        assert_eq!(&map.masked_image()[..4], &[70, 70, 70, 255]);
+        map.with_fog_shade(1.0, 1.0).unwrap();
+        assert_eq!(&map.masked_image()[..4], &[100, 150, 200, 255]);
+
+        match map.with_fog_texture(vec![0; 4], 2, 1) {
+            Ok(_) => panic!("Expected an error, but got a valid texture"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "The fog texture must have 8 bytes for a 2x1 image, but has 4 bytes"
+            ),
+        }
+        match map.with_fog_shade(1.5, 1.0) {
+            Ok(_) => panic!("Expected an error, but got a valid shade"),
+            Err(e) => assert_eq!(e.to_string(), "Brightness must be between 0 and 1, got 1.5"),
+        }
+
+        map.with_fog_texture(vec![1, 2, 3, 4], 1, 1).unwrap();
+        let restored = Map::from_binary(&map.to_binary(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false).fog, map.state(false).fog);
+    }
+
+    #[test]
+    fn test_explored_map() {
+        let mut map = Map::new(
+            [100, 100, 100, 255].repeat(100 * 20),
+            100,
+            20,
+            10,
+            MapType::Explored,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let pixel =
+            |image: &[u8], x: usize| image[(10 * 100 + x) * 4..(10 * 100 + x) * 4 + 4].to_vec();
+
+        assert!(map.unlock_point_from_coordinates(10, 10));
+        assert!(map.unlock_point_from_coordinates(80, 10));
+        assert_eq!(map.unlocked(), &[(10, 10), (80, 10)]);
+        assert_eq!(map.current_position(), Some((80, 10)));
+        let image = map.masked_image();
+        assert_eq!(pixel(&image, 80), vec![100, 100, 100, 255]); // Visible
+        assert_eq!(pixel(&image, 10), vec![50, 50, 50, 255]); // Explored
+        assert_eq!(pixel(&image, 45), vec![0, 0, 0, 255]); // Unexplored
+
+        // Going back to an explored point makes it visible again without unlocking it
+        assert!(!map.unlock_point_from_coordinates(10, 10));
+        assert_eq!(map.current_position(), Some((10, 10)));
+        map.with_explored_fog(0.0).unwrap();
+        let image = map.masked_image();
+        assert_eq!(pixel(&image, 10), vec![100, 100, 100, 255]);
+        assert_eq!(pixel(&image, 80), vec![100, 100, 100, 255]);
+
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.current_position(), Some((10, 10)));
+        assert_eq!(restored.state(false), map.state(false));
+        match map.with_explored_fog(2.0) {
+            Ok(_) => panic!("Expected an error, but got a valid opacity"),
+            Err(e) => assert_eq!(e.to_string(), "Opacity must be between 0 and 1, got 2"),
+        }
+    }
+
+    #[test]
+    fn test_reveal_shapes() {
+        let new = |special_points| {
+            Map::new(
+                [100, 100, 100, 255].repeat(100 * 100),
+                100,
+                100,
+                10,
+                MapType::Limited,
+                vec![],
+                special_points,
+                vec![],
+            )
+            .unwrap()
+        };
+        let revealed =
+            |map: &mut Map, x: usize, y: usize| map.masked_image()[(y * 100 + x) * 4] != 0;
+
+        let mut map = new(vec![]);
+        assert!(map
+            .unlock_point_with_shape(50, 50, RevealShape::Square(20))
+            .unwrap());
+        assert!(revealed(&mut map, 69, 69));
+        assert!(!revealed(&mut map, 71, 50));
+
+        let mut map = new(vec![]);
+        map.with_reveal_shape((50, 50), RevealShape::Ellipse(30, 5))
+            .unwrap();
+        map.unlock_point_from_coordinates(50, 50);
+        assert!(revealed(&mut map, 79, 50));
+        assert!(!revealed(&mut map, 50, 56));
+
+        let mut map = new(vec![]);
+        let triangle = RevealShape::Polygon(vec![(0, -20), (20, 20), (-20, 20)]);
+        map.unlock_point_with_shape(50, 50, triangle.clone())
+            .unwrap();
+        assert!(revealed(&mut map, 50, 65));
+        assert!(!revealed(&mut map, 40, 35));
+
+        // Shapes larger than the map are only checked where they cover it
+        let mut map = new(vec![]);
+        let huge = RevealShape::Polygon(vec![(-140, -140), (140, -140), (0, 140)]);
+        map.unlock_point_with_shape(0, 99, huge).unwrap();
+        assert!(revealed(&mut map, 0, 99));
+        assert!(revealed(&mut map, 99, 0));
+
+        // But they cannot extend further than the diagonal of the map
+        for shape in [
+            RevealShape::Circle(50000),
+            RevealShape::Ellipse(1, u32::MAX),
+            RevealShape::Polygon(vec![(0, 0), (i32::MIN, 0), (0, 1)]),
+        ] {
+            match map.unlock_point_with_shape(50, 50, shape.clone()) {
+                Ok(_) => panic!("Expected an error, but got a valid shape"),
+                Err(e) => assert!(e
+                    .to_string()
+                    .starts_with("A reveal shape can extend at most 142 pixels")),
+            }
+            assert!(map.with_reveal_shape((50, 50), shape).is_err());
+        }
+        map.with_line_of_sight(true);
+        map.unlock_point_with_shape(50, 50, RevealShape::Circle(142))
+            .unwrap();
+        assert!(revealed(&mut map, 99, 99));
+
+        // The shape of the unlocked point replaces that of its special point
+        let mut map = new(vec![(52, 52)]);
+        map.with_reveal_shape((52, 52), RevealShape::Circle(30))
+            .unwrap();
+        map.unlock_point_with_shape(50, 50, RevealShape::Square(3))
+            .unwrap();
+        assert!(revealed(&mut map, 53, 47));
+        assert!(!revealed(&mut map, 52, 60));
+
+        // The shape of a special point replaces its large circle
+        let mut map = new(vec![(50, 50)]);
+        map.unlock_point_from_coordinates(50, 50);
+        assert!(revealed(&mut map, 50, 80));
+        map.with_reveal_shape((50, 50), RevealShape::Circle(3))
+            .unwrap();
+        assert!(revealed(&mut map, 50, 53));
+        assert!(!revealed(&mut map, 50, 54));
+
+        map.with_reveal_shape((10, 10), triangle).unwrap();
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false), map.state(false));
+
+        match map.with_reveal_shape((50, 50), RevealShape::Polygon(vec![(0, 0), (1, 1)])) {
+            Ok(_) => panic!("Expected an error, but got a valid shape"),
+            Err(e) => assert_eq!(e.to_string(), "A polygon needs at least 3 points, got 2"),
+        }
+        match map.unlock_point_with_shape(50, 50, RevealShape::Ellipse(0, 5)) {
+            Ok(_) => panic!("Expected an error, but got a valid shape"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Both radii of an ellipse must be at least 1, got 0 and 5"
+            ),
+        }
+    }
+
+    #[test]
+    fn test_regions() {
+        let mut map = Map::new(
+            [100, 100, 100, 255].repeat(100 * 100),
+            100,
+            100,
+            10,
+            MapType::Hidden,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let revealed =
+            |map: &mut Map, x: usize, y: usize| map.masked_image()[(y * 100 + x) * 4] != 0;
+        map.with_region("Sunken Crypt", vec![(0, 0), (60, 0), (0, 60)])
+            .unwrap();
+
+        assert!(!revealed(&mut map, 10, 10));
+        assert!(map.unlock_region("Sunken Crypt").unwrap());
+        assert!(!map.unlock_region("Sunken Crypt").unwrap());
+        assert_eq!(
+            map.unlocked_regions().collect::<Vec<_>>(),
+            vec!["Sunken Crypt"]
+        );
+        // Everything inside the polygon is revealed, nothing outside of it
+        assert!(revealed(&mut map, 10, 10));
+        assert!(revealed(&mut map, 29, 29));
+        assert!(!revealed(&mut map, 31, 31));
+
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false), map.state(false));
+
+        assert!(map.lock_region("Sunken Crypt").unwrap());
+        assert!(!revealed(&mut map, 10, 10));
+        assert!(!map.lock_region("Sunken Crypt").unwrap());
+
+        match map.unlock_region("Kingdom of Ardan") {
+            Ok(_) => panic!("Expected an error, but got a region"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "There is no region named \"Kingdom of Ardan\""
+            ),
+        }
+        assert!(matches!(
+            map.with_region("Kingdom of Ardan", vec![(0, 0), (100, 0), (0, 50)]),
+            Err(RpgMapError::OutOfBounds {
+                point: (100, 0),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_lock_and_decay() {
+        let mut map = Map::new(
+            [100, 100, 100, 255].repeat(100 * 20),
+            100,
+            20,
+            10,
+            MapType::Limited,
+            vec![(10, 10)],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let revealed = |map: &mut Map, x: usize| map.masked_image()[(10 * 100 + x) * 4] != 0;
+
+        map.unlock_point_from_coordinates(50, 10);
+        assert!(map.lock_point_from_coordinates(52, 9));
+        assert!(!map.lock_point_from_coordinates(50, 10));
+        assert_eq!(map.unlocked(), &[(10, 10)]);
+        assert!(!revealed(&mut map, 50));
+
+        // Without decay points stay unlocked
+        assert!(map.tick(100).is_empty());
+
+        map.with_decay(Some(3));
+        map.unlock_point_from_coordinates(50, 10);
+        // (10, 10) was never visited and counts as seen at tick 0
+        assert_eq!(map.tick(2), vec![(10, 10)]);
+        map.unlock_point_from_coordinates(80, 10);
+        assert_eq!(map.tick(1), vec![(50, 10)]);
+        assert_eq!(map.unlocked(), &[(80, 10)]);
+        assert!(!revealed(&mut map, 50));
+        assert!(revealed(&mut map, 80));
+
+        // Visiting a point again keeps it from decaying
+        map.tick(2);
+        map.unlock_point_from_coordinates(80, 10);
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false), map.state(false));
+        assert!(map.tick(2).is_empty());
+        assert_eq!(map.tick(1), vec![(80, 10)]);
+        assert!(map.unlocked().is_empty());
+
+        // Locking the current position leaves none, also after a round trip
+        let mut map = Map::new(
+            [100, 100, 100, 255].repeat(100 * 20),
+            100,
+            20,
+            10,
+            MapType::Explored,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        map.unlock_point_from_coordinates(10, 10);
+        map.unlock_point_from_coordinates(80, 10);
+        map.lock_point_from_coordinates(80, 10);
+        assert_eq!(map.current_position(), None);
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.current_position(), None);
+        assert_eq!(restored.state(false), map.state(false));
+        let restored = Map::from_binary(&map.to_binary(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false), map.state(false));
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_map_accepts_buffers() {
+        use pyo3::prelude::{PyAnyMethods, PyDictMethods, Python};
+        use pyo3::types::{PyBytes, PyDict};
+
+        let (image, image_width, image_height) = get_image_bits("test_assets", "cat.png");
+        let mut map = Map::new(
+            image.clone(),
+            image_width,
+            image_height,
+            4,
+            MapType::Full,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let expected = map.get_bits();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            locals.set_item("image", PyBytes::new(py, &image)).unwrap();
+            locals.set_item("Map", py.get_type::<Map>()).unwrap();
+            locals.set_item("width", image_width).unwrap();
+            locals.set_item("height", image_height).unwrap();
+            for buffer in [
+                c"image",
+                c"bytearray(image)",
+                c"memoryview(image)",
+                c"list(image)",
+            ] {
+                locals
+                    .set_item("buffer", py.eval(buffer, None, Some(&locals)).unwrap())
+                    .unwrap();
+                let bits = py
+                    .eval(
+                        c"Map(buffer, width, height, 4).get_bits()",
+                        None,
+                        Some(&locals),
+                    )
+                    .unwrap();
+                assert!(bits.is_instance_of::<PyBytes>());
+                assert_eq!(bits.extract::<Vec<u8>>().unwrap(), expected);
+            }
+        });
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_png_round_trip() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let data = std::fs::read("test_assets/map.png").unwrap();
+        let mut map =
+            Map::from_png_bytes(&data, 20, MapType::Full, vec![], vec![], vec![]).unwrap();
+        assert_eq!((map.width(), map.height()), (image_width, image_height));
+        assert_eq!(map.bytes(), image);
+
+        let from_file = Map::from_file(
+            "test_assets/map.png".as_ref(),
+            20,
+            MapType::Full,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(from_file.bytes(), image);
+
+        let expected = map.get_bits();
+        let png = map.to_png_bytes(None).unwrap();
+        let decoded = crate::structs::encoding::decode(&png).unwrap();
+        assert_eq!(decoded, (expected, image_width, image_height));
+
+        match map.to_png_bytes(Some(vec![0; 4])) {
+            Ok(_) => panic!("Expected an error, but got a PNG"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Image must have the same size as the map (1412000 bytes), but has 4 bytes"
+            ),
+        }
+        match Map::from_png_bytes(&[1, 2, 3], 20, MapType::Full, vec![], vec![], vec![]) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert!(e.to_string().starts_with("Invalid image")),
         }
     }
 }
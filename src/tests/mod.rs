@@ -1,4 +1,9 @@
+pub mod exploration;
+pub mod hierarchy;
 pub mod map;
+pub mod navigation;
 pub mod path;
+pub mod state;
 pub mod travel;
 pub mod utils;
+pub mod vision;
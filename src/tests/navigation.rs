@@ -0,0 +1,95 @@
+use super::utils::get_image_bits;
+
+#[cfg(test)]
+mod navigation_tests {
+    use super::*;
+    use crate::structs::map::Map;
+    use crate::structs::map::MapType;
+    use crate::structs::travel::{Travel, TravelOptions};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    fn count_obstacles(map: &Map) -> usize {
+        map.navigation_grid()
+            .grid
+            .iter()
+            .map(|row| row.iter().filter(|cell| **cell == 1).count())
+            .sum()
+    }
+
+    #[test]
+    fn test_navigation_grid_is_cached() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let grid = map.navigation_grid();
+        // Travels on copies of the map share the same grid
+        Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&grid, &map.navigation_grid()));
+        assert_eq!(grid.grid[250][158], 0);
+
+        // Changing the buffer invalidates the grid
+        map.with_buffer(0, None, BTreeMap::new()).unwrap();
+        let new_grid = map.navigation_grid();
+        assert!(!Arc::ptr_eq(&grid, &new_grid));
+        assert_ne!(new_grid.grid, grid.grid);
+        assert!(Arc::ptr_eq(&new_grid, &map.navigation_grid()));
+
+        // So does setting a cost layer
+        let costs = map.bytes().to_vec();
+        map.with_cost_map(costs).unwrap();
+        assert!(!Arc::ptr_eq(&new_grid, &map.navigation_grid()));
+        assert!(map.navigation_grid().costs.is_some());
+    }
+
+    #[test]
+    fn test_configurable_buffer() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+        let default_obstacles = count_obstacles(&map);
+
+        // Less clearance around the polygon
+        map.with_buffer(5, Some(0), BTreeMap::new()).unwrap();
+        let less = count_obstacles(&map);
+        assert!(less < default_obstacles);
+
+        // More clearance around the polygon by overriding it
+        map.with_buffer(5, Some(0), BTreeMap::from([(0, 10)]))
+            .unwrap();
+        assert!(count_obstacles(&map) > default_obstacles);
+
+        // Less clearance around the edges
+        map.with_buffer(2, Some(0), BTreeMap::new()).unwrap();
+        assert!(count_obstacles(&map) < less);
+
+        match map.with_buffer(5, None, BTreeMap::from([(1, 10)])) {
+            Ok(_) => panic!("Expected an error, but got a valid map"),
+            Err(e) => assert_eq!(e.to_string(), "There is no obstacle with index 1"),
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use crate::structs::path::astar;
 use crate::structs::path::PathPoint;
+use crate::structs::path::{astar, line_between, movement_range, smooth_path, weighted_astar};
 
 #[test]
 fn test_astar_diagonal_path() {
     let grid = vec![vec![2, 0, 0], vec![0, 0, 0], vec![0, 0, 3]];
 
-    let path = astar(&grid).unwrap();
+    let path = astar(&grid, (0, 0), (2, 2)).unwrap();
     assert_eq!(
         path,
         vec![
@@ -26,7 +26,7 @@ fn test_astar_example() {
         vec![0, 0, 0, 0, 0],
     ];
 
-    let path = astar(&grid).unwrap();
+    let path = astar(&grid, (0, 2), (4, 2)).unwrap();
     assert_eq!(
         path,
         vec![
@@ -45,7 +45,7 @@ fn test_astar_example() {
         vec![1, 0, 1, 1, 0],
         vec![2, 0, 0, 0, 0],
     ];
-    let path = astar(&grid).unwrap();
+    let path = astar(&grid, (0, 4), (4, 2)).unwrap();
     assert_eq!(
         path,
         vec![
@@ -57,3 +57,89 @@ fn test_astar_example() {
         ]
     );
 }
+
+#[test]
+fn test_weighted_astar_avoids_expensive_terrain() {
+    let grid = vec![
+        vec![0, 0, 0, 0, 0],
+        vec![2, 0, 0, 0, 3],
+        vec![0, 0, 0, 0, 0],
+    ];
+    let costs = vec![
+        vec![1, 1, 1, 1, 1],
+        vec![1, 50, 50, 50, 1],
+        vec![9, 9, 9, 9, 9],
+    ];
+
+    let path = weighted_astar(&grid, &costs, (0, 1), (4, 1)).unwrap();
+    assert_eq!(
+        path,
+        vec![
+            PathPoint::from_tuple((0, 1)),
+            PathPoint::from_tuple((1, 0)),
+            PathPoint::from_tuple((2, 0)),
+            PathPoint::from_tuple((3, 0)),
+            PathPoint::from_tuple((4, 1))
+        ]
+    );
+
+    // Without costs the direct route is taken
+    let path = astar(&grid, (0, 1), (4, 1)).unwrap();
+    assert!(path.iter().all(|p| p.y == 1));
+}
+
+#[test]
+fn test_smooth_path() {
+    let mut grid = vec![vec![0; 10]; 5];
+    grid[0][0] = 2;
+    grid[3][9] = 3;
+
+    let path = astar(&grid, (0, 0), (9, 3)).unwrap();
+    let smoothed = smooth_path(&grid, None, &path);
+    assert_eq!(
+        smoothed,
+        line_between(PathPoint::from_tuple((0, 0)), PathPoint::from_tuple((9, 3)))
+    );
+
+    // The straight line is blocked, so the smoothed path has to go around
+    grid[2][4] = 1;
+    grid[1][4] = 1;
+    let path = astar(&grid, (0, 0), (9, 3)).unwrap();
+    let smoothed = smooth_path(&grid, None, &path);
+    assert_eq!(smoothed.first(), path.first());
+    assert_eq!(smoothed.last(), path.last());
+    assert!(smoothed.len() <= path.len());
+    for pair in smoothed.windows(2) {
+        assert!(pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1);
+        assert_ne!(grid[pair[1].y as usize][pair[1].x as usize], 1);
+    }
+}
+
+#[test]
+fn test_movement_range() {
+    let grid = vec![
+        vec![0, 0, 0, 0, 0],
+        vec![0, 0, 1, 0, 0],
+        vec![0, 0, 0, 0, 0],
+    ];
+
+    let range = movement_range(&grid, None, (0, 1), 1);
+    assert_eq!(range.len(), 6);
+    assert_eq!(range[&(0, 1)], 0);
+    assert!(range.values().all(|distance| *distance <= 1));
+
+    // The obstacle has to be walked around
+    let range = movement_range(&grid, None, (1, 1), 2);
+    assert!(!range.contains_key(&(2, 1)));
+    assert_eq!(range[&(3, 1)], 2);
+
+    // Expensive terrain uses up more of the budget
+    let costs = vec![
+        vec![1, 1, 1, 1, 1],
+        vec![1, 5, 1, 1, 1],
+        vec![1, 1, 1, 1, 1],
+    ];
+    let range = movement_range(&grid, Some(&costs), (0, 1), 4);
+    assert!(!range.contains_key(&(1, 1)));
+    assert_eq!(range[&(4, 0)], 4);
+}
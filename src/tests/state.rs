@@ -0,0 +1,235 @@
+use super::utils::get_image_bits;
+
+#[cfg(test)]
+mod state_tests {
+    use super::*;
+    use crate::structs::map::Map;
+    use crate::structs::map::MapType;
+    use crate::structs::path::PathPoint;
+    use crate::structs::state::{MapState, TravelState, STATE_VERSION};
+    use crate::structs::travel::{Travel, TravelOptions};
+
+    fn explored_map() -> Map {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![(200, 400)],
+            vec![(300, 500)],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+        map.unlock_point_from_coordinates(330, 512);
+        map.with_dot(198, 390, [255, 0, 0, 255], 5)
+            .with_grid()
+            .with_obstacles()
+            .with_buffer(3, Some(1), [(0, 2)].into())
+            .unwrap();
+        map
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut map = explored_map();
+        let state = map.state(true);
+
+        let from_json = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(from_json.state(true), state);
+        let from_binary = Map::from_binary(&map.to_binary(true).unwrap(), None).unwrap();
+        assert_eq!(from_binary.state(true), state);
+        assert_eq!(
+            MapState::from_binary(&state.to_binary().unwrap()).unwrap(),
+            state
+        );
+
+        // The restored map draws the same image
+        let mut restored = Map::from_state(state, None).unwrap();
+        assert_eq!(restored.get_bits(), map.get_bits());
+    }
+
+    #[test]
+    fn test_state_without_bytes() {
+        let map = explored_map();
+        let json = map.to_json(false).unwrap();
+        assert!(json.len() < map.bytes().len());
+
+        let restored = Map::from_json(&json, Some(map.bytes().to_vec())).unwrap();
+        assert_eq!(restored.state(true), map.state(true));
+
+        match Map::from_json(&json, None) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "The map was serialized without its bytes, so they have to be passed in"
+            ),
+        }
+        let mut other_bytes = map.bytes().to_vec();
+        other_bytes[0] ^= 1;
+        match Map::from_json(&json, Some(other_bytes)) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(e.to_string(), "The bytes do not match the serialized map"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_state() {
+        let map = explored_map();
+        let mut state = map.state(true);
+        state.cost_map = Some(vec![1; 3]);
+        match Map::from_state(state, None) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Cost map must have the same size as the map (353000 bytes), but has 3 bytes"
+            ),
+        }
+
+        let mut state = map.state(true);
+        state.obstacle_buffers.insert(5, 1);
+        match Map::from_state(state, None) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(e.to_string(), "There is no obstacle with index 5"),
+        }
+
+        let mut state = map.state(true);
+        state.clock = 3;
+        state.last_seen = vec![((200, 400), 5)];
+        match Map::from_state(state, None) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Point (200, 400) was last seen at tick 5, after the current tick 3"
+            ),
+        }
+    }
+
+    #[test]
+    fn test_version_1_states() {
+        // Written by version 1, before the fog and exploration settings were added
+        let data = std::fs::read("test_assets/state_v1.bin").unwrap();
+        let map = Map::from_binary(&data, None).unwrap();
+        assert_eq!(map.unlocked(), [(5, 5), (10, 10)]);
+        assert_eq!(map.current_position(), Some((10, 10)));
+        assert_eq!(map.buffer_of_obstacle(0), 2);
+        assert_eq!(map.state(false).version, STATE_VERSION);
+
+        let data = std::fs::read("test_assets/travel_v1.bin").unwrap();
+        let travel = Travel::from_binary(&data).unwrap();
+        assert_eq!(travel.computed_path().first(), Some(&PathPoint::new(0, 0)));
+        assert_eq!(travel.computed_path().last(), Some(&PathPoint::new(19, 19)));
+        assert_eq!(travel.map().state(true), map.state(true));
+
+        // Newer versions cannot be read
+        let mut data = map.to_binary(true).unwrap();
+        data[..4].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+        match Map::from_binary(&data, None) {
+            Ok(_) => panic!("Expected an error, but got a map"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                format!(
+                    "State version {} is not supported, the newest supported version is {}",
+                    STATE_VERSION + 1,
+                    STATE_VERSION
+                )
+            ),
+        }
+    }
+
+    #[test]
+    fn test_travel_round_trip() {
+        let map = explored_map();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
+
+        let restored = Travel::from_binary(&travel.to_binary().unwrap()).unwrap();
+        assert_eq!(restored.computed_path(), travel.computed_path());
+        assert_eq!(restored.legs(), travel.legs());
+        assert_eq!(restored.map().state(true), map.state(true));
+
+        // A travel without a path cannot be drawn
+        let state = TravelState {
+            version: STATE_VERSION,
+            map: map.state(true),
+            computed_path: vec![],
+            legs: vec![],
+            snap_distance: None,
+        };
+        match Travel::from_binary(&state.to_binary().unwrap()) {
+            Ok(_) => panic!("Expected an error, but got a travel"),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "The path of the travel is empty or does not match its legs"
+            ),
+        }
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_pickle_and_copy() {
+        use pyo3::prelude::*;
+        use pyo3::wrap_pymodule;
+
+        let map = explored_map();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            // Pickle looks the classes up in their module
+            let module = wrap_pymodule!(crate::python::rpg_map)(py);
+            py.import("sys")
+                .unwrap()
+                .getattr("modules")
+                .unwrap()
+                .set_item("rpg_map", module)
+                .unwrap();
+            let pickle = py.import("pickle").unwrap();
+            let copy = py.import("copy").unwrap();
+            let round_trip = |object: Py<PyAny>| {
+                let pickled = pickle.call_method1("dumps", (object,)).unwrap();
+                pickle.call_method1("loads", (pickled,)).unwrap().unbind()
+            };
+
+            let pickled = round_trip(Py::new(py, map.clone()).unwrap().into_any());
+            let restored: Map = pickled.extract(py).unwrap();
+            assert_eq!(restored.state(true), map.state(true));
+
+            let pickled = round_trip(Py::new(py, travel.clone()).unwrap().into_any());
+            let restored: Travel = pickled.extract(py).unwrap();
+            assert_eq!(restored.computed_path(), travel.computed_path());
+            assert_eq!(restored.legs(), travel.legs());
+            assert_eq!(restored.map().state(true), map.state(true));
+
+            let point = Py::new(py, PathPoint::new(3, 4)).unwrap().into_any();
+            let restored: PathPoint = round_trip(point.clone_ref(py)).extract(py).unwrap();
+            assert_eq!(restored, PathPoint::new(3, 4));
+
+            for function in ["copy", "deepcopy"] {
+                let copied = copy.call_method1(function, (point.clone_ref(py),)).unwrap();
+                assert!(!copied.is(&point));
+                assert_eq!(copied.extract::<PathPoint>().unwrap(), PathPoint::new(3, 4));
+
+                let map_object = Py::new(py, map.clone()).unwrap();
+                let copied: Map = copy
+                    .call_method1(function, (map_object,))
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(copied.state(true), map.state(true));
+            }
+        });
+    }
+}
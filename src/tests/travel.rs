@@ -3,9 +3,14 @@ use super::utils::{compare_images, get_image_bits};
 #[cfg(test)]
 mod travel_tests {
     use super::*;
+    use crate::structs::error::RpgMapError;
     use crate::structs::map::Map;
     use crate::structs::map::MapType;
-    use crate::structs::travel::Travel;
+    use crate::structs::map::PathDisplayType;
+    use crate::structs::map::PathProgressDisplayType;
+    use crate::structs::map::PathStyle;
+    use crate::structs::path::PathPoint;
+    use crate::structs::travel::{Travel, TravelOptions};
 
     #[test]
     fn test_dbg_map() {
@@ -20,7 +25,8 @@ mod travel_tests {
             vec![],
             vec![],
             vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
-        );
+        )
+        .unwrap();
         let result = Travel::dbg_map(map);
         compare_images(&result, &expected, &image, image_width, image_height);
     }
@@ -37,38 +43,645 @@ mod travel_tests {
             vec![],
             vec![],
             vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
-        );
+        )
+        .unwrap();
         // Test going into the obstacle
-        match Travel::new(map.clone(), (198, 390), (158, 250)) {
+        match Travel::new(
+            map.clone(),
+            (198, 390),
+            (158, 250),
+            TravelOptions::default(),
+        ) {
             Ok(_) => panic!("Expected an error, but got a valid travel object"),
-            Err(e) => assert_eq!(
-                e.to_string(),
-                "ValueError: Current location or destination is an obstacle"
-            ),
+            Err(e) => {
+                assert!(matches!(
+                    e,
+                    RpgMapError::BlockedEndpoint { point: (158, 250) }
+                ));
+                assert_eq!(e.to_string(), "Point (158, 250) is an obstacle");
+            }
         }
 
         // Test going into the boarder
-        match Travel::new(map.clone(), (198, 390), (100, 425)) {
+        match Travel::new(
+            map.clone(),
+            (198, 390),
+            (100, 425),
+            TravelOptions::default(),
+        ) {
+            Ok(_) => panic!("Expected an error, but got a valid travel object"),
+            Err(e) => assert!(matches!(
+                e,
+                RpgMapError::BlockedEndpoint { point: (100, 425) }
+            )),
+        }
+
+        // Test going out of bounds
+        match Travel::new(
+            map.clone(),
+            (198, 390),
+            (1000, 1000),
+            TravelOptions::default(),
+        ) {
+            Ok(_) => panic!("Expected an error, but got a valid travel object"),
+            Err(e) => {
+                assert!(matches!(
+                    e,
+                    RpgMapError::OutOfBounds {
+                        point: (1000, 1000),
+                        width: 500,
+                        height: 706
+                    }
+                ));
+                assert_eq!(
+                    e.to_string(),
+                    "Point (1000, 1000) is out of bounds of the 500x706 map"
+                );
+            }
+        }
+
+        // Test going to unreachable island
+        match Travel::new(map.clone(), (198, 390), (60, 90), TravelOptions::default()) {
+            Ok(_) => panic!("Expected an error, but got a valid travel object"),
+            Err(e) => {
+                assert!(matches!(
+                    e,
+                    RpgMapError::NoPath {
+                        start: (198, 390),
+                        destination: (60, 90)
+                    }
+                ));
+                assert_eq!(e.to_string(), "No path found from (198, 390) to (60, 90)");
+            }
+        }
+    }
+
+    #[test]
+    fn test_travel_from_waypoints() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let waypoints = vec![(198, 390), (330, 512), (172, 223)];
+        let travel =
+            Travel::from_waypoints(map.clone(), waypoints.clone(), TravelOptions::default())
+                .unwrap();
+
+        assert_eq!(travel.legs().len(), 2);
+        assert_eq!(travel.legs()[0].0, 0);
+        assert_eq!(travel.legs()[0].1, travel.legs()[1].0);
+        assert_eq!(travel.legs()[1].1, travel.computed_path().len() - 1);
+        for (i, (start, _)) in travel.legs().iter().enumerate() {
+            let point = travel.computed_path()[*start];
+            assert_eq!((point.x, point.y), waypoints[i]);
+        }
+
+        // Each leg is the same as a travel between its waypoints
+        let first = Travel::new(
+            map.clone(),
+            waypoints[0],
+            waypoints[1],
+            TravelOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            travel.computed_path()[..=travel.legs()[0].1],
+            first.computed_path()[..]
+        );
+
+        // A repeated waypoint is a leg of a single point
+        let waypoints = vec![(198, 390), (198, 390), (330, 512)];
+        let travel =
+            Travel::from_waypoints(map.clone(), waypoints.clone(), TravelOptions::default())
+                .unwrap();
+        assert_eq!(
+            travel.legs(),
+            vec![(0, 0), (0, travel.computed_path().len() - 1)]
+        );
+        assert_eq!(
+            travel.computed_path(),
+            Travel::new(
+                map.clone(),
+                waypoints[1],
+                waypoints[2],
+                TravelOptions::default()
+            )
+            .unwrap()
+            .computed_path()
+        );
+
+        match Travel::from_waypoints(map, vec![(198, 390)], TravelOptions::default()) {
+            Ok(_) => panic!("Expected an error, but got a valid travel object"),
+            Err(e) => assert_eq!(e.to_string(), "At least two waypoints are required"),
+        }
+    }
+
+    #[test]
+    fn test_smooth_travel() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (172, 223),
+            TravelOptions::default(),
+        )
+        .unwrap();
+        let smooth = Travel::new(
+            map.clone(),
+            (198, 390),
+            (172, 223),
+            TravelOptions {
+                smooth: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            smooth.computed_path().first(),
+            travel.computed_path().first()
+        );
+        assert_eq!(smooth.computed_path().last(), travel.computed_path().last());
+        assert!(smooth.computed_path().len() <= travel.computed_path().len());
+
+        let grid = &map.navigation_grid().grid;
+        for pair in smooth.computed_path().windows(2) {
+            assert!(pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1);
+            assert_eq!(grid[pair[1].y as usize][pair[1].x as usize], 0);
+        }
+    }
+
+    #[test]
+    fn test_snap_destination() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+        let grid = &map.navigation_grid().grid;
+
+        // A reachable destination is not moved
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions {
+                snap_destination: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(travel.snap_distance(), None);
+
+        // Going into the obstacle or to the unreachable island
+        for destination in [(158, 250), (60, 90)] {
+            let travel = Travel::new(
+                map.clone(),
+                (198, 390),
+                destination,
+                TravelOptions {
+                    snap_destination: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let end = travel.computed_path().last().unwrap();
+            assert_ne!((end.x, end.y), destination);
+            assert_eq!(grid[end.y as usize][end.x as usize], 0);
+
+            let dx = end.x as f32 - destination.0 as f32;
+            let dy = end.y as f32 - destination.1 as f32;
+            assert_eq!(travel.snap_distance(), Some((dx * dx + dy * dy).sqrt()));
+        }
+
+        // Going out of bounds is still an error
+        match Travel::new(
+            map.clone(),
+            (198, 390),
+            (1000, 1000),
+            TravelOptions {
+                snap_destination: true,
+                ..Default::default()
+            },
+        ) {
             Ok(_) => panic!("Expected an error, but got a valid travel object"),
             Err(e) => assert_eq!(
                 e.to_string(),
-                "ValueError: Current location or destination is an obstacle"
+                "Point (1000, 1000) is out of bounds of the 500x706 map"
             ),
         }
+    }
 
-        // Test going out of bounds
-        match Travel::new(map.clone(), (198, 390), (1000, 1000)) {
-            Ok(_) => panic!("Expected an error, but got a valid travel object"),
+    #[test]
+    fn test_reachability() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+
+        assert!(Travel::is_reachable(&map, (198, 390), (330, 512)).unwrap());
+        // The island and the obstacle
+        assert!(!Travel::is_reachable(&map, (198, 390), (60, 90)).unwrap());
+        assert!(!Travel::is_reachable(&map, (198, 390), (158, 250)).unwrap());
+
+        let mainland = Travel::region_of(&map, (198, 390)).unwrap();
+        assert!(mainland.is_some());
+        assert_eq!(Travel::region_of(&map, (330, 512)).unwrap(), mainland);
+        assert_eq!(Travel::region_of(&map, (158, 250)).unwrap(), None);
+
+        match Travel::region_of(&map, (1000, 1000)) {
+            Ok(_) => panic!("Expected an error, but got a region"),
             Err(e) => assert_eq!(
                 e.to_string(),
-                "ValueError: Current location or destination is out of bounds"
+                "Point (1000, 1000) is out of bounds of the 500x706 map"
             ),
         }
+    }
 
-        // Test going to unreachable island
-        match Travel::new(map.clone(), (198, 390), (60, 90)) {
+    #[test]
+    fn test_movement_range() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Full,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let range = Travel::movement_range(&map, (198, 390), 30).unwrap();
+        assert_eq!(range.distances[&(198, 390)], 0);
+        assert_eq!(range.distances[&(228, 390)], 30);
+        assert!(!range.distances.contains_key(&(229, 390)));
+        assert!(range.distances.values().all(|distance| *distance <= 30));
+
+        // No path is shorter than the distance to its destination
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (210, 410),
+            TravelOptions::default(),
+        )
+        .unwrap();
+        assert!((range.distances[&(210, 410)] as usize) < travel.computed_path().len());
+
+        let plain = map.get_bits();
+        let tinted = map.with_movement_range(&range, [0, 0, 255, 128]).get_bits();
+
+        let index = (390 * image_width + 198) as usize * 4;
+        assert_ne!(plain[index..index + 4], tinted[index..index + 4]);
+        let index = (390 * image_width + 300) as usize * 4;
+        assert_eq!(plain[index..index + 4], tinted[index..index + 4]);
+    }
+
+    #[test]
+    fn test_hierarchical_travel() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+        let hierarchical = Travel::new(
+            map.clone(),
+            (198, 390),
+            (172, 223),
+            TravelOptions {
+                hierarchical: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // Finding a path does not label the regions of the whole map
+        assert!(!map.navigation_grid().has_regions());
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (172, 223),
+            TravelOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hierarchical.computed_path().first(),
+            travel.computed_path().first()
+        );
+        assert_eq!(
+            hierarchical.computed_path().last(),
+            travel.computed_path().last()
+        );
+        assert!(hierarchical.computed_path().len() <= travel.computed_path().len() * 5 / 4);
+
+        let grid = &map.navigation_grid().grid;
+        for pair in hierarchical.computed_path().windows(2) {
+            assert!(pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1);
+            assert_eq!(grid[pair[1].y as usize][pair[1].x as usize], 0);
+        }
+
+        match Travel::new(
+            map,
+            (198, 390),
+            (60, 90),
+            TravelOptions {
+                hierarchical: true,
+                ..Default::default()
+            },
+        ) {
             Ok(_) => panic!("Expected an error, but got a valid travel object"),
-            Err(e) => assert_eq!(e.to_string(), "ValueError: No path found"),
+            Err(e) => assert_eq!(e.to_string(), "No path found from (198, 390) to (60, 90)"),
         }
     }
+
+    #[test]
+    fn test_travel_to_start() {
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+
+        // Both modes return the single point, which can be drawn in every style
+        for hierarchical in [false, true] {
+            let travel = Travel::new(
+                map.clone(),
+                (198, 390),
+                (198, 390),
+                TravelOptions {
+                    hierarchical,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(travel.computed_path(), vec![PathPoint::new(198, 390)]);
+            assert_eq!(travel.legs(), vec![(0, 0)]);
+
+            for style in [
+                PathStyle::Debug(),
+                PathStyle::Solid([255, 0, 0, 255]),
+                PathStyle::Dotted([255, 0, 0, 255]),
+                PathStyle::SolidWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
+                PathStyle::DottedWithOutline([255, 0, 0, 255], [255, 255, 255, 255]),
+            ] {
+                map.clone()
+                    .draw_path(
+                        travel.clone(),
+                        1.0,
+                        2,
+                        style,
+                        PathDisplayType::BelowMask,
+                        PathProgressDisplayType::Progress,
+                    )
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_travel_crossing_corner() {
+        // Two opaque quadrants which only touch at the corner of the clusters at (32, 32)
+        let image = (0..64 * 64)
+            .flat_map(|i| {
+                let (x, y) = (i % 64, i / 64);
+                let alpha = if (x < 32) == (y < 32) { 255 } else { 0 };
+                [255, 255, 255, alpha]
+            })
+            .collect::<Vec<u8>>();
+        let mut map =
+            Map::new(image, 64, 64, 20, MapType::Limited, vec![], vec![], vec![]).unwrap();
+        map.with_buffer(0, None, Default::default()).unwrap();
+
+        let travel = Travel::new(map.clone(), (5, 5), (60, 60), TravelOptions::default()).unwrap();
+        let hierarchical = Travel::new(
+            map.clone(),
+            (5, 5),
+            (60, 60),
+            TravelOptions {
+                hierarchical: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(Travel::is_reachable(&map, (5, 5), (60, 60)).unwrap());
+        assert_eq!(
+            hierarchical.computed_path().len(),
+            travel.computed_path().len()
+        );
+        assert!(hierarchical
+            .computed_path()
+            .contains(&PathPoint::new(31, 31)));
+        assert!(hierarchical
+            .computed_path()
+            .contains(&PathPoint::new(32, 32)));
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_entry_points() {
+        use crate::structs::path::PathPoint;
+        use pyo3::prelude::{Py, PyAnyMethods, Python};
+
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let mut map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let travel = Travel::new(
+            map.clone(),
+            (198, 390),
+            (330, 512),
+            TravelOptions::default(),
+        )
+        .unwrap();
+
+        // The wrappers which release the GIL return the same as the Rust methods
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let from_python = py
+                .get_type::<Travel>()
+                .call1((map.clone(), (198, 390), (330, 512)))
+                .unwrap();
+            let path: Vec<PathPoint> = from_python
+                .getattr("computed_path")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(path, travel.computed_path());
+
+            let dbg: Vec<u8> = py
+                .get_type::<Travel>()
+                .call_method1("dbg_map", (map.clone(),))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(dbg, Travel::dbg_map(map.clone()));
+
+            let bits: Vec<u8> = Py::new(py, map.clone())
+                .unwrap()
+                .call_method0(py, "get_bits")
+                .unwrap()
+                .extract(py)
+                .unwrap();
+            assert_eq!(bits, map.get_bits());
+
+            // The queries borrow the map object instead of copying it
+            let shared = Py::new(py, map.clone()).unwrap();
+            let travel_type = py.get_type::<Travel>();
+            let reachable: bool = travel_type
+                .call_method1(
+                    "is_reachable",
+                    (shared.clone_ref(py), (198, 390), (330, 512)),
+                )
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(reachable);
+            let region: Option<u32> = travel_type
+                .call_method1("region_of", (shared.clone_ref(py), (198, 390)))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(region, Travel::region_of(&map, (198, 390)).unwrap());
+            let range = travel_type
+                .call_method1("movement_range", (shared, (198, 390), 5))
+                .unwrap();
+            let budget: u32 = range.getattr("budget").unwrap().extract().unwrap();
+            assert_eq!(budget, 5);
+        });
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_exceptions() {
+        use pyo3::prelude::{PyAnyMethods, PyErr, PyModule, Python};
+        use pyo3::wrap_pymodule;
+
+        let (image, image_width, image_height) = get_image_bits("test_assets", "map.png");
+        let map = Map::new(
+            image,
+            image_width,
+            image_height,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(160, 240), (134, 253), (234, 257), (208, 239)]],
+        )
+        .unwrap();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = wrap_pymodule!(crate::python::rpg_map)(py);
+            let module = module.bind(py).downcast::<PyModule>().unwrap().clone();
+            let value_error = py.get_type::<pyo3::exceptions::PyValueError>();
+            let raise = |destination: (u32, u32)| -> PyErr {
+                module
+                    .getattr("Travel")
+                    .unwrap()
+                    .call1((map.clone(), (198, 390), destination))
+                    .unwrap_err()
+            };
+
+            let error = raise((1000, 1000));
+            assert!(error.is_instance(py, &module.getattr("OutOfBoundsError").unwrap()));
+            assert!(error.is_instance(py, &value_error));
+            let value = error.value(py);
+            let point: (u32, u32) = value.getattr("point").unwrap().extract().unwrap();
+            let width: u32 = value.getattr("width").unwrap().extract().unwrap();
+            assert_eq!((point, width), ((1000, 1000), image_width));
+
+            let error = raise((158, 250));
+            assert!(error.is_instance(py, &module.getattr("BlockedEndpointError").unwrap()));
+            let point: (u32, u32) = error.value(py).getattr("point").unwrap().extract().unwrap();
+            assert_eq!(point, (158, 250));
+
+            let error = raise((60, 90));
+            assert!(error.is_instance(py, &module.getattr("NoPathError").unwrap()));
+            let destination: (u32, u32) = error
+                .value(py)
+                .getattr("destination")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(destination, (60, 90));
+
+            let error = module
+                .getattr("Map")
+                .unwrap()
+                .call_method1("draw_background", (vec![0u8; 4], vec![0u8; 8]))
+                .unwrap_err();
+            assert!(error.is_instance(py, &module.getattr("SizeMismatchError").unwrap()));
+            let sizes: (usize, usize) = (
+                error
+                    .value(py)
+                    .getattr("expected")
+                    .unwrap()
+                    .extract()
+                    .unwrap(),
+                error
+                    .value(py)
+                    .getattr("actual")
+                    .unwrap()
+                    .extract()
+                    .unwrap(),
+            );
+            assert_eq!(sizes, (4, 8));
+        });
+    }
 }
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod vision_tests {
+    use crate::structs::map::Map;
+    use crate::structs::map::MapType;
+    use crate::structs::vision::{occluders, visible_area};
+
+    /// An 80x40 map with a wall from x = 30 to 34 and a transparent column at x = 5
+    fn walled_map() -> Map {
+        let mut bytes = [100, 100, 100, 255].repeat(80 * 40);
+        for y in 0..40 {
+            bytes[(y * 80 + 5) * 4 + 3] = 0;
+        }
+        Map::new(
+            bytes,
+            80,
+            40,
+            20,
+            MapType::Limited,
+            vec![],
+            vec![],
+            vec![vec![(30, 0), (34, 0), (34, 39), (30, 39)]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_occluders() {
+        let occluders = occluders(&walled_map());
+        assert_eq!(occluders[20][5], 1); // Transparent
+        assert_eq!(occluders[20][32], 1); // Obstacle
+        assert_eq!(occluders[20][20], 0);
+    }
+
+    #[test]
+    fn test_visible_area() {
+        let occluders = occluders(&walled_map());
+        let visible = visible_area(&occluders, (20, 20), 25);
+        let side = 51;
+        let is_visible =
+            |x: i32, y: i32| visible[(y - 20 + 25) as usize * side + (x - 20 + 25) as usize];
+        assert!(is_visible(20, 20));
+        assert!(is_visible(29, 20));
+        assert!(is_visible(30, 20)); // The face of the wall
+        assert!(!is_visible(40, 20));
+        assert!(is_visible(5, 20));
+        assert!(!is_visible(2, 20));
+        // Outside of the map
+        assert!(!is_visible(20, -3));
+    }
+
+    #[test]
+    fn test_line_of_sight_mask() {
+        let mut map = walled_map();
+        map.unlock_point_from_coordinates(20, 20);
+        assert_eq!(map.unlocked(), &[(20, 20)]);
+        let pixel =
+            |image: &[u8], x: usize| image[(20 * 80 + x) * 4..(20 * 80 + x) * 4 + 4].to_vec();
+
+        // Without line of sight the circle reaches behind the wall
+        let image = map.masked_image();
+        assert_eq!(pixel(&image, 40), vec![100, 100, 100, 255]);
+        assert_eq!(pixel(&image, 2), vec![100, 100, 100, 255]);
+
+        map.with_line_of_sight(true);
+        let image = map.masked_image();
+        assert_eq!(pixel(&image, 25), vec![100, 100, 100, 255]);
+        assert_eq!(pixel(&image, 40), vec![0, 0, 0, 255]);
+        assert_eq!(pixel(&image, 2), vec![0, 0, 0, 255]);
+
+        let restored = Map::from_json(&map.to_json(true).unwrap(), None).unwrap();
+        assert_eq!(restored.state(false), map.state(false));
+    }
+}